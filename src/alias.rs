@@ -0,0 +1,72 @@
+use crate::config::ProjectPattern;
+use crate::model::{Date, DateRange, DayEntry, Project, ProjectTimes};
+use anyhow::{Result, ensure};
+use derive_getters::Getters;
+use im::Vector;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+enum AliasError {
+    #[error("Alias patterns must have the same number of components: {0} => {1}")]
+    MismatchedPatterns(ProjectPattern, ProjectPattern),
+}
+
+/// Maps projects matching an old pattern onto a new one.  Only the components
+/// present in the patterns are replaced so sub-projects are carried over when
+/// renaming a project and projects are carried over when renaming a client.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Alias {
+    from: ProjectPattern,
+    to: ProjectPattern,
+    dates: DateRange,
+}
+
+impl Alias {
+    pub fn new(from: ProjectPattern, to: ProjectPattern, dates: DateRange) -> Result<Self> {
+        ensure!(
+            from.specificity() == to.specificity(),
+            AliasError::MismatchedPatterns(from, to)
+        );
+        Ok(Alias { from, to, dates })
+    }
+
+    /// The renamed project if the alias applies to the project on the date.
+    pub fn rename(&self, project: &Project, date: &Date) -> Option<Project> {
+        if !self.dates.contains(date) || !self.from.matches(project) {
+            return None;
+        }
+        Some(Project::new(
+            self.to.client(),
+            self.to.code().as_deref().unwrap_or(project.code()),
+            self.to.subcode().as_deref().unwrap_or(project.subcode()),
+        ))
+    }
+}
+
+fn canonical_project(aliases: &Vector<Alias>, project: &Project, date: &Date) -> Project {
+    aliases
+        .iter()
+        .find_map(|a| a.rename(project, date))
+        .unwrap_or_else(|| project.clone())
+}
+
+/// Replaces aliased projects in the day entries with their canonical projects.
+pub fn apply_aliases(aliases: &Vector<Alias>, day_entries: &Vector<DayEntry>) -> Vector<DayEntry> {
+    if aliases.is_empty() {
+        return day_entries.clone();
+    }
+    day_entries
+        .iter()
+        .map(|e| {
+            let projects: Vector<ProjectTimes> = e
+                .projects()
+                .iter()
+                .map(|p| p.with_project(canonical_project(aliases, p.project(), e.date())))
+                .collect();
+            DayEntry::new(*e.date(), &projects, *e.line_number())
+        })
+        .collect()
+}
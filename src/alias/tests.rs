@@ -0,0 +1,69 @@
+use super::*;
+use crate::model::fixtures::date;
+use im::vector;
+
+#[test]
+fn test_rename() {
+    let alias = Alias::new(
+        ProjectPattern::new("acme", Some("content"), None),
+        ProjectPattern::new("acme", Some("cms"), None),
+        DateRange::new(date(2025, 1, 1), date(2025, 6, 30)),
+    )
+    .unwrap();
+    let d = date(2025, 3, 1);
+    assert_eq!(
+        Some(Project::new("acme", "cms", "")),
+        alias.rename(&Project::new("acme", "content", ""), &d)
+    );
+    assert_eq!(
+        Some(Project::new("acme", "cms", "fix")),
+        alias.rename(&Project::new("acme", "content", "fix"), &d)
+    );
+    assert_eq!(None, alias.rename(&Project::new("acme", "web", ""), &d));
+    assert_eq!(
+        None,
+        alias.rename(&Project::new("acme", "content", ""), &date(2025, 7, 1))
+    );
+
+    let client = Alias::new(
+        ProjectPattern::new("acme", None, None),
+        ProjectPattern::new("acmecorp", None, None),
+        DateRange::new(Date::min_date(), Date::max_date()),
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Project::new("acmecorp", "web", "")),
+        client.rename(&Project::new("acme", "web", ""), &d)
+    );
+
+    assert!(
+        Alias::new(
+            ProjectPattern::new("acme", None, None),
+            ProjectPattern::new("acme", Some("cms"), None),
+            DateRange::new(Date::min_date(), Date::max_date()),
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_apply_aliases() {
+    let alias = Alias::new(
+        ProjectPattern::new("acme", Some("content"), None),
+        ProjectPattern::new("acme", Some("cms"), None),
+        DateRange::new(Date::min_date(), Date::max_date()),
+    )
+    .unwrap();
+    let times = |code| ProjectTimes::new(Project::new("acme", code, ""), &vector!()).unwrap();
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 3, 1),
+        &vector!(times("content"), times("web")),
+        7
+    ));
+    let expected = vector!(DayEntry::new(
+        date(2025, 3, 1),
+        &vector!(times("cms"), times("web")),
+        7
+    ));
+    assert_eq!(expected, apply_aliases(&vector!(alias), &day_entries));
+}
@@ -0,0 +1,95 @@
+use crate::model::{Date, DayEntry, format_hours};
+use crate::registry::Registry;
+use crate::report::day_billable_minutes;
+use derive_getters::Getters;
+use im::{OrdMap, OrdSet, Vector};
+
+#[cfg(test)]
+mod tests;
+
+/// Start date of a running flex-time balance along with an optional number of
+/// hours per week above which billable time counts as overtime.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Balance {
+    from: Date,
+    weekly_overtime_minutes: Option<u32>,
+}
+
+impl Balance {
+    pub fn new(from: Date, weekly_overtime_minutes: Option<u32>) -> Self {
+        Balance {
+            from,
+            weekly_overtime_minutes,
+        }
+    }
+}
+
+/// Billable and expected time from the start of a balance through a given date.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct BalanceStatus {
+    balance: Balance,
+    through: Date,
+    billable_minutes: u32,
+    expected_minutes: u32,
+    overtime_minutes: Option<u32>,
+}
+
+impl BalanceStatus {
+    /// Billable minutes less expected minutes, positive when ahead.
+    pub fn delta_minutes(&self) -> i32 {
+        self.billable_minutes as i32 - self.expected_minutes as i32
+    }
+
+    /// One line summary for status displays.
+    pub fn summary(&self) -> String {
+        let mut answer = format!(
+            "Balance {} since {}",
+            format_delta(self.delta_minutes()),
+            self.balance.from
+        );
+        if let Some(overtime) = self.overtime_minutes {
+            answer += &format!(", overtime {}", format_hours(overtime));
+        }
+        answer
+    }
+}
+
+fn format_delta(minutes: i32) -> String {
+    let sign = if minutes < 0 { "-" } else { "+" };
+    format!("{sign}{}", format_hours(minutes.unsigned_abs()))
+}
+
+/// Computes the balance through a date.  Like the report delta, eight hours
+/// are expected for each weekday with time recorded.  Overtime is the billable
+/// time above the weekly threshold summed over every week.
+pub fn compute_balance_status(
+    balance: &Balance,
+    day_entries: &Vector<DayEntry>,
+    registry: &Registry,
+    through: Date,
+) -> BalanceStatus {
+    let mut billable = 0;
+    let mut weekdays = OrdSet::<Date>::new();
+    let mut weeks = OrdMap::<u32, u32>::new();
+    for entry in day_entries
+        .iter()
+        .filter(|e| *e.date() >= balance.from && *e.date() <= through)
+    {
+        let minutes = day_billable_minutes(entry, |p| registry.is_billable(p));
+        billable += minutes;
+        if entry.date().is_weekday() {
+            weekdays.insert(*entry.date());
+        }
+        *weeks.entry(entry.date().week_num()).or_default() += minutes;
+    }
+    let overtime_minutes = balance
+        .weekly_overtime_minutes
+        .map(|limit| weeks.values().map(|m| m.saturating_sub(limit)).sum());
+    BalanceStatus {
+        balance: balance.clone(),
+        through,
+        billable_minutes: billable,
+        expected_minutes: 480 * weekdays.len() as u32,
+        overtime_minutes,
+    }
+}
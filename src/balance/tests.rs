@@ -0,0 +1,60 @@
+use super::*;
+use crate::config::ProjectPattern;
+use crate::model::fixtures::{date, project_times, time_range};
+use crate::model::{DateRange, Project};
+use crate::registry::ProjectInfo;
+use im::vector;
+
+fn entry(day: u8, code: &str, hours: u16) -> DayEntry {
+    let project = Project::new("acme", code, "");
+    DayEntry::new(
+        date(2025, 7, day),
+        &vector!(project_times(
+            &project,
+            vector!(time_range(8, 0, 8 + hours, 5))
+        )),
+        day as u32,
+    )
+}
+
+#[test]
+fn test_balance_status() {
+    let registry = Registry::new(&vector!(ProjectInfo::new(
+        ProjectPattern::new("acme", Some("internal"), None),
+        "Internal",
+        DateRange::new(Date::min_date(), Date::max_date()),
+        false
+    )));
+    // Friday 07/04 is before the start and Saturday 07/12 is not expected.
+    let day_entries = vector!(
+        entry(4, "cms", 9),
+        entry(7, "cms", 10),
+        entry(8, "cms", 10),
+        entry(9, "cms", 10),
+        entry(10, "cms", 10),
+        entry(10, "internal", 1),
+        entry(11, "cms", 6),
+        entry(12, "cms", 4),
+        entry(14, "cms", 7)
+    );
+    let balance = Balance::new(date(2025, 7, 7), Some(40 * 60));
+    let status = compute_balance_status(&balance, &day_entries, &registry, date(2025, 7, 14));
+    assert_eq!(57 * 60, *status.billable_minutes());
+    assert_eq!(6 * 480, *status.expected_minutes());
+    assert_eq!(9 * 60, status.delta_minutes());
+    assert_eq!(Some(10 * 60), *status.overtime_minutes());
+    assert_eq!(
+        "Balance +9:00 since 07/07/2025, overtime 10:00",
+        status.summary()
+    );
+
+    let status = compute_balance_status(
+        &Balance::new(date(2025, 7, 11), None),
+        &day_entries,
+        &registry,
+        date(2025, 7, 11),
+    );
+    assert_eq!(-120, status.delta_minutes());
+    assert_eq!(None, *status.overtime_minutes());
+    assert_eq!("Balance -2:00 since 07/11/2025", status.summary());
+}
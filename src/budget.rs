@@ -0,0 +1,134 @@
+use crate::check::{Diagnostic, Severity};
+use crate::config::ProjectPattern;
+use crate::model::{Date, DateRange, DayEntry, format_hours};
+use crate::report::day_billable_minutes;
+use derive_getters::Getters;
+use im::{OrdMap, Vector};
+
+#[cfg(test)]
+mod tests;
+
+/// Fixed number of hours available to all projects matching a pattern within a date window.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Budget {
+    pattern: ProjectPattern,
+    minutes: u32,
+    dates: DateRange,
+}
+
+impl Budget {
+    pub fn new(pattern: ProjectPattern, minutes: u32, dates: DateRange) -> Self {
+        Budget {
+            pattern,
+            minutes,
+            dates,
+        }
+    }
+}
+
+/// Consumption of a budget as of a given date.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct BudgetStatus {
+    budget: Budget,
+    used_minutes: u32,
+    exhaustion: Option<Date>,
+}
+
+impl BudgetStatus {
+    pub fn remaining_minutes(&self) -> i32 {
+        self.budget.minutes as i32 - self.used_minutes as i32
+    }
+
+    pub fn percent_used(&self) -> u32 {
+        (self.used_minutes * 100)
+            .checked_div(self.budget.minutes)
+            .unwrap_or(100)
+    }
+
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        let percent = self.percent_used();
+        let kind = if percent >= 100 {
+            "budget exceeded"
+        } else if percent >= 80 {
+            "budget nearly used"
+        } else {
+            return None;
+        };
+        let detail = format!(
+            "{} has used {}% of {} hours",
+            self.budget.pattern,
+            percent,
+            format_hours(self.budget.minutes)
+        );
+        Some(Diagnostic::new(Severity::Warning, kind, None, &detail))
+    }
+}
+
+/// Billable minutes per date for projects matching the budget within its window
+/// and on or before a date.
+fn daily_minutes(
+    budget: &Budget,
+    day_entries: &Vector<DayEntry>,
+    as_of: Date,
+) -> OrdMap<Date, u32> {
+    let mut minutes = OrdMap::<Date, u32>::new();
+    for entry in day_entries
+        .iter()
+        .filter(|e| budget.dates.contains(e.date()) && *e.date() <= as_of)
+    {
+        let billable = day_billable_minutes(entry, |p| budget.pattern.matches(p));
+        if billable > 0 {
+            *minutes.entry(*entry.date()).or_insert(0) += billable;
+        }
+    }
+    minutes
+}
+
+/// Date on which the budget ran out or, if hours remain, the date on which it
+/// will run out at the average daily burn rate since work on it started.
+fn exhaustion_date(budget: &Budget, daily: &OrdMap<Date, u32>, as_of: Date) -> Option<Date> {
+    let mut used = 0;
+    for (date, minutes) in daily {
+        used += minutes;
+        if used >= budget.minutes {
+            return Some(*date);
+        }
+    }
+    let start = *daily.keys().next()?;
+    let as_of = as_of.min(*budget.dates.last()).max(start);
+    let elapsed_days = (as_of.day_num() - start.day_num() + 1) as u64;
+    let remaining = (budget.minutes - used) as u64;
+    let days_left = (remaining * elapsed_days).div_ceil(used as u64);
+    as_of.plus_days(days_left as i32).ok()
+}
+
+/// Computes the status of each budget as of a date, counting only time logged
+/// on or before it.
+pub fn compute_budget_statuses(
+    budgets: &Vector<Budget>,
+    day_entries: &Vector<DayEntry>,
+    as_of: Date,
+) -> Vector<BudgetStatus> {
+    budgets
+        .iter()
+        .map(|budget| {
+            let daily = daily_minutes(budget, day_entries, as_of);
+            BudgetStatus {
+                budget: budget.clone(),
+                used_minutes: daily.values().sum(),
+                exhaustion: exhaustion_date(budget, &daily, as_of),
+            }
+        })
+        .collect()
+}
+
+pub fn budget_diagnostics(
+    budgets: &Vector<Budget>,
+    day_entries: &Vector<DayEntry>,
+    today: Date,
+) -> Vector<Diagnostic> {
+    compute_budget_statuses(budgets, day_entries, today)
+        .iter()
+        .filter_map(|s| s.diagnostic())
+        .collect()
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::model::Project;
+use crate::model::fixtures::{date, project_times, time_range};
+use im::vector;
+
+fn day_entry(d: Date, code: &str, hours: u16) -> DayEntry {
+    let project = Project::new("acme", code, "");
+    let times = project_times(&project, vector!(time_range(8, 0, 8 + hours, 5)));
+    DayEntry::new(d, &vector!(times), 0)
+}
+
+#[test]
+fn test_budget_status() {
+    let day_entries = vector!(
+        day_entry(date(2025, 6, 30), "cms", 4),
+        day_entry(date(2025, 7, 1), "cms", 4),
+        day_entry(date(2025, 7, 2), "web", 4),
+        day_entry(date(2025, 7, 3), "cms", 4)
+    );
+    let budget = Budget::new(
+        ProjectPattern::new("acme", Some("cms"), None),
+        20 * 60,
+        DateRange::new(date(2025, 7, 1), date(2025, 7, 31)),
+    );
+    let statuses =
+        compute_budget_statuses(&vector!(budget.clone()), &day_entries, date(2025, 7, 4));
+    let status = &statuses[0];
+    assert_eq!(8 * 60, status.used_minutes);
+    assert_eq!(12 * 60, status.remaining_minutes());
+    assert_eq!(40, status.percent_used());
+    assert_eq!(None, status.diagnostic());
+    // 8 hours over 4 days leaves 6 more days of work
+    assert_eq!(Some(date(2025, 7, 10)), status.exhaustion);
+
+    // As of the end of an earlier report only the time logged by then counts.
+    let statuses =
+        compute_budget_statuses(&vector!(budget.clone()), &day_entries, date(2025, 7, 2));
+    assert_eq!(4 * 60, statuses[0].used_minutes);
+    assert_eq!(Some(date(2025, 7, 10)), statuses[0].exhaustion);
+
+    let small = Budget::new(budget.pattern.clone(), 10 * 60, budget.dates);
+    let diagnostics = budget_diagnostics(&vector!(small), &day_entries, date(2025, 7, 4));
+    assert_eq!(
+        "budget nearly used: acme,cms has used 80% of 10:00 hours",
+        diagnostics[0].to_string()
+    );
+
+    let tiny = Budget::new(budget.pattern.clone(), 6 * 60, budget.dates);
+    let statuses = compute_budget_statuses(&vector!(tiny), &day_entries, date(2025, 7, 4));
+    assert_eq!(Some(date(2025, 7, 3)), statuses[0].exhaustion);
+    assert_eq!(-120, statuses[0].remaining_minutes());
+    assert!(statuses[0].diagnostic().unwrap().kind() == "budget exceeded");
+}
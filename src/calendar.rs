@@ -0,0 +1,112 @@
+use crate::check::{Diagnostic, Severity};
+use crate::model::{DAY_ABBREVS, Date, DateRange, DayEntry};
+use anyhow::Result;
+use derive_getters::Getters;
+use im::{OrdMap, OrdSet, Vector};
+
+#[cfg(test)]
+mod tests;
+
+/// Monday through Friday.
+const WEEKDAY_COUNT: usize = 5;
+
+/// Days of the week normally worked along with holidays on which no time is
+/// expected.  Days are named by their abbreviation (e.g., "MON").
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct WorkCalendar {
+    workdays: OrdSet<String>,
+    holidays: OrdSet<Date>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> Self {
+        WorkCalendar {
+            workdays: DAY_ABBREVS.iter().take(WEEKDAY_COUNT).cloned().collect(),
+            holidays: OrdSet::new(),
+        }
+    }
+}
+
+impl WorkCalendar {
+    pub fn new(workdays: &OrdSet<String>, holidays: &OrdSet<Date>) -> Self {
+        WorkCalendar {
+            workdays: workdays.clone(),
+            holidays: holidays.clone(),
+        }
+    }
+
+    /// True when time is expected to be recorded on the date.
+    pub fn is_workday(&self, date: &Date) -> bool {
+        self.workdays.contains(&date.day_abbrev()) && !self.holidays.contains(date)
+    }
+}
+
+/// Warnings for each workday in the date range with no date block or with a
+/// date block that has no time recorded.
+pub fn missing_day_diagnostics(
+    calendar: &WorkCalendar,
+    dates: &DateRange,
+    day_entries: &Vector<DayEntry>,
+) -> Vector<Diagnostic> {
+    let mut minutes = OrdMap::<Date, (u32, u32)>::new();
+    for entry in day_entries.iter().filter(|e| dates.contains(e.date())) {
+        let total: u32 = entry.projects().iter().map(|p| p.total_minutes()).sum();
+        let (m, _) = minutes
+            .entry(*entry.date())
+            .or_insert((0, *entry.line_number()));
+        *m += total;
+    }
+    dates
+        .iter()
+        .filter(|d| calendar.is_workday(d))
+        .filter_map(|d| {
+            let detail = format!("{} {d} has no time recorded", d.day_abbrev());
+            match minutes.get(&d) {
+                None => Some(Diagnostic::new(
+                    Severity::Warning,
+                    "missing day",
+                    None,
+                    &detail,
+                )),
+                Some((0, line_num)) => Some(Diagnostic::new(
+                    Severity::Warning,
+                    "empty day",
+                    Some(*line_num),
+                    &detail,
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Missing day warnings for the requested dates from the first date logged
+/// through yesterday, leaving today for time not yet recorded.
+pub fn recent_missing_day_diagnostics(
+    calendar: &WorkCalendar,
+    dates: &DateRange,
+    day_entries: &Vector<DayEntry>,
+    today: Date,
+) -> Result<Vector<Diagnostic>> {
+    let yesterday = today.prev()?;
+    let Some(logged) = logged_dates(day_entries) else {
+        return Ok(Vector::new());
+    };
+    let first = *dates.first().max(logged.first());
+    let last = *dates.last().min(&yesterday);
+    if first > last {
+        return Ok(Vector::new());
+    }
+    Ok(missing_day_diagnostics(
+        calendar,
+        &DateRange::new(first, last),
+        day_entries,
+    ))
+}
+
+/// Dates from the first through the last date block of the time log.
+pub fn logged_dates(day_entries: &Vector<DayEntry>) -> Option<DateRange> {
+    let first = day_entries.iter().map(|e| *e.date()).min()?;
+    let last = day_entries.iter().map(|e| *e.date()).max()?;
+    Some(DateRange::new(first, last))
+}
@@ -0,0 +1,72 @@
+use super::*;
+use crate::model::Project;
+use crate::model::fixtures::{date, project_times, time_range};
+use im::{ordset, vector};
+
+fn entry(d: u8, minutes: u16) -> DayEntry {
+    let range = time_range(8, 0, 8 + minutes / 60, minutes % 60);
+    DayEntry::new(
+        date(2025, 7, d),
+        &vector!(project_times(
+            &Project::new("acme", "cms", ""),
+            vector!(range)
+        )),
+        d as u32 * 3,
+    )
+}
+
+fn messages(diagnostics: Vector<Diagnostic>) -> Vector<String> {
+    diagnostics.iter().map(|d| d.to_string()).collect()
+}
+
+#[test]
+fn test_missing_day_warnings() {
+    // 07/04/2025 is a Friday and 07/05 and 07/06 fall on a weekend.
+    let day_entries = vector!(entry(1, 60), entry(2, 0), entry(7, 30));
+    let dates = logged_dates(&day_entries).unwrap();
+    assert_eq!(DateRange::new(date(2025, 7, 1), date(2025, 7, 7)), dates);
+    assert_eq!(
+        vector!(
+            "empty day:6: WED 07/02/2025 has no time recorded".to_string(),
+            "missing day: THU 07/03/2025 has no time recorded".to_string(),
+            "missing day: FRI 07/04/2025 has no time recorded".to_string()
+        ),
+        messages(missing_day_diagnostics(
+            &WorkCalendar::default(),
+            &dates,
+            &day_entries
+        ))
+    );
+
+    let calendar = WorkCalendar::new(
+        &ordset!("MON".to_string(), "TUE".to_string(), "THU".to_string()),
+        &ordset!(date(2025, 7, 3)),
+    );
+    assert!(!calendar.is_workday(&date(2025, 7, 2)));
+    assert!(missing_day_diagnostics(&calendar, &dates, &day_entries).is_empty());
+    assert_eq!(None, logged_dates(&Vector::new()));
+
+    // Only the requested dates before today and after the first logged date count.
+    let calendar = WorkCalendar::default();
+    let recent = |first, last, today| {
+        messages(
+            recent_missing_day_diagnostics(
+                &calendar,
+                &DateRange::new(date(2025, 6, first), date(2025, 7, last)),
+                &day_entries,
+                date(2025, 7, today),
+            )
+            .unwrap(),
+        )
+    };
+    assert_eq!(
+        vector!(
+            "empty day:6: WED 07/02/2025 has no time recorded".to_string(),
+            "missing day: THU 07/03/2025 has no time recorded".to_string()
+        ),
+        recent(20, 3, 10)
+    );
+    assert_eq!(2, recent(20, 31, 4).len());
+    assert!(recent(20, 1, 10).is_empty());
+    assert!(recent(20, 3, 1).is_empty());
+}
@@ -0,0 +1,112 @@
+use derive_getters::Getters;
+use im::Vector;
+use serde_json::json;
+use std::fmt::Display;
+
+#[cfg(test)]
+mod tests;
+
+/// How serious a problem is.  Errors make the `check` command fail.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A problem found in a time log, usually tied to one of its lines.
+#[derive(Clone, PartialEq, Eq, Debug, Getters)]
+pub struct Diagnostic {
+    severity: Severity,
+    kind: String,
+    line_number: Option<u32>,
+    detail: String,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, kind: &str, line_number: Option<u32>, detail: &str) -> Self {
+        Diagnostic {
+            severity,
+            kind: kind.to_string(),
+            line_number,
+            detail: detail.to_string(),
+        }
+    }
+
+    /// Message in the form "file:line: severity: kind: detail" used by editors
+    /// and other tools.
+    pub fn format_for_file(&self, filename: &str) -> String {
+        let location = match self.line_number {
+            Some(line) => format!("{filename}:{line}"),
+            None => filename.to_string(),
+        };
+        format!(
+            "{location}: {}: {}: {}",
+            self.severity.name(),
+            self.kind,
+            self.detail
+        )
+    }
+}
+
+/// Displays the diagnostic in the same form as the warnings found while parsing.
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line_number {
+            Some(line) => write!(f, "{}:{line}: {}", self.kind, self.detail),
+            None => write!(f, "{}: {}", self.kind, self.detail),
+        }
+    }
+}
+
+pub fn error_count(diagnostics: &Vector<Diagnostic>) -> usize {
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count()
+}
+
+/// Diagnostics one per line followed by a summary line.
+pub fn render_check_text(filename: &str, diagnostics: &Vector<Diagnostic>) -> Vector<String> {
+    let mut answer: Vector<String> = diagnostics
+        .iter()
+        .map(|d| d.format_for_file(filename))
+        .collect();
+    let errors = error_count(diagnostics);
+    answer.push_back(format!(
+        "{filename}: {errors} errors, {} warnings",
+        diagnostics.len() - errors
+    ));
+    answer
+}
+
+/// Diagnostics as a JSON document for scripts.
+pub fn render_check_json(
+    filename: &str,
+    diagnostics: &Vector<Diagnostic>,
+) -> serde_json::Result<String> {
+    let errors = error_count(diagnostics);
+    let document = json!({
+        "file": filename,
+        "errors": errors,
+        "warnings": diagnostics.len() - errors,
+        "diagnostics": diagnostics
+            .iter()
+            .map(|d| json!({
+                "line": d.line_number,
+                "severity": d.severity.name(),
+                "kind": d.kind,
+                "message": d.detail,
+            }))
+            .collect::<Vec<_>>(),
+    });
+    serde_json::to_string_pretty(&document)
+}
@@ -0,0 +1,39 @@
+use super::*;
+use im::vector;
+
+#[test]
+fn test_display() {
+    let diagnostic = Diagnostic::new(
+        Severity::Warning,
+        "out of order dates",
+        Some(12),
+        "prev='07/08/2025'",
+    );
+    assert_eq!(
+        "out of order dates:12: prev='07/08/2025'",
+        diagnostic.to_string()
+    );
+    let diagnostic = Diagnostic::new(Severity::Warning, "budget exceeded", None, "acme");
+    assert_eq!("budget exceeded: acme", diagnostic.to_string());
+}
+
+#[test]
+fn test_render_check() {
+    let diagnostics = vector!(
+        Diagnostic::new(Severity::Error, "parse error", Some(3), "Invalid time line"),
+        Diagnostic::new(Severity::Warning, "budget exceeded", None, "acme")
+    );
+    assert_eq!(
+        vector!(
+            "times.txt:3: error: parse error: Invalid time line".to_string(),
+            "times.txt: warning: budget exceeded: acme".to_string(),
+            "times.txt: 1 errors, 1 warnings".to_string()
+        ),
+        render_check_text("times.txt", &diagnostics)
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&render_check_json("times.txt", &diagnostics).unwrap()).unwrap();
+    assert_eq!(1, json["errors"]);
+    assert_eq!(3, json["diagnostics"][0]["line"]);
+    assert_eq!(serde_json::Value::Null, json["diagnostics"][1]["line"]);
+}
@@ -0,0 +1,589 @@
+use crate::alias::Alias;
+use crate::balance::Balance;
+use crate::budget::{Budget, budget_diagnostics};
+use crate::calendar::{WorkCalendar, recent_missing_day_diagnostics};
+use crate::check::{Diagnostic, Severity};
+use crate::gaps::{GapRules, gap_diagnostics};
+use crate::model::{Date, DateRange, DayEntry, Project, Time, TimeRange};
+use crate::parse::remove_comments;
+use crate::rates::{Rate, Rates};
+use crate::registry::{ProjectInfo, Registry, registry_diagnostics};
+use crate::retainer::Retainer;
+use crate::sanity::{SanityCheck, SanityRule, sanity_diagnostics};
+use crate::target::Target;
+use anyhow::{Context, Result, bail};
+use derive_getters::Getters;
+use im::{OrdMap, OrdSet, Vector};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::fmt::Display;
+use std::fs::{File, exists};
+use std::io::{self, BufRead};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+enum ConfigError {
+    #[error("Unable to open config file {0}: {1}")]
+    OpenFileFailure(String, #[source] io::Error),
+    #[error("Unable to read line from config file: {0}")]
+    ReadFileFailure(#[from] io::Error),
+    #[error("Invalid config line:{0}: {1}")]
+    InvalidLine(u32, String),
+    #[error("Invalid rate: {0}")]
+    InvalidRate(String),
+    #[error("Invalid budget: {0}")]
+    InvalidBudget(String),
+    #[error("Invalid hours: {0}")]
+    InvalidHours(String),
+    #[error("Invalid alias: {0}")]
+    InvalidAlias(String),
+    #[error("Invalid project: {0}")]
+    InvalidProject(String),
+    #[error("Invalid client: {0}")]
+    InvalidClient(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Invalid balance: {0}")]
+    InvalidBalance(String),
+    #[error("Duplicate balance: {0}")]
+    DuplicateBalance(String),
+    #[error("Invalid break: {0}")]
+    InvalidBreak(String),
+    #[error("Duplicate gaps: {0}")]
+    DuplicateGaps(String),
+    #[error("Invalid holiday: {0}")]
+    InvalidHoliday(String),
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("Duplicate schedule: {0}")]
+    DuplicateSchedule(String),
+    #[error("Invalid check: {0}")]
+    InvalidCheck(String),
+    #[error("Invalid target: {0}")]
+    InvalidTarget(String),
+    #[error("Invalid retainer: {0}")]
+    InvalidRetainer(String),
+    #[error("Duplicate retainer: {0}")]
+    DuplicateRetainer(String),
+}
+
+const PROJECT_PATTERN: &str =
+    r"(?<client>[a-z]+)(,(?<code>[-/ A-Za-z0-9]+?)(,(?<subcode>[-/ A-Za-z0-9]+?))?)?";
+const DATE_PATTERN: &str = r"\d{2}/\d{2}/\d{4}";
+
+lazy_static! {
+    static ref CONFIG_LINE_RE: Regex = Regex::new(r"^(?<kind>[A-Z][a-z]+): *(?<body>.*)$").unwrap();
+    static ref AMOUNT_RE: Regex = Regex::new(r"^(\d+)(\.(\d{1,2}))?$").unwrap();
+    static ref RATE_RE: Regex = Regex::new(&format!(
+        r"^{PROJECT_PATTERN} *: *(?<amount>[.0-9]+) +(?<currency>[A-Z]{{3}})( +from +(?<from>{DATE_PATTERN}))?$"
+    ))
+    .unwrap();
+    static ref HOURS_RE: Regex = Regex::new(r"^(\d+)(:(\d{2}))?$").unwrap();
+    static ref BUDGET_RE: Regex = Regex::new(&format!(
+        r"^{PROJECT_PATTERN} *: *(?<hours>[:0-9]+)( +from +(?<from>{DATE_PATTERN}))?( +to +(?<to>{DATE_PATTERN}))?$"
+    ))
+    .unwrap();
+    static ref PROJECT_PATTERN_RE: Regex = Regex::new(&format!(r"^{PROJECT_PATTERN}$")).unwrap();
+    static ref ALIAS_RE: Regex = Regex::new(&format!(
+        r"^(?<old>[^=]+?) *=> *(?<new>[^=]+?)( +from +(?<from>{DATE_PATTERN}))?( +to +(?<to>{DATE_PATTERN}))?$"
+    ))
+    .unwrap();
+    static ref PROJECT_INFO_RE: Regex = Regex::new(&format!(
+        r"^{PROJECT_PATTERN} *= *(?<name>.+?)( +from +(?<from>{DATE_PATTERN}))?( +to +(?<to>{DATE_PATTERN}))?( +(?<billing>billable|nonbillable))?$"
+    ))
+    .unwrap();
+    static ref BALANCE_RE: Regex = Regex::new(&format!(
+        r"^from +(?<from>{DATE_PATTERN})( +overtime +(?<overtime>[:0-9]+))?$"
+    ))
+    .unwrap();
+    static ref RETAINER_RE: Regex = Regex::new(&format!(
+        r"^(?<client>[a-z]+) *: *(?<hours>[:0-9]+)( +rollover +(?<rollover>[:0-9]+))?( +from +(?<from>{DATE_PATTERN}))?$"
+    ))
+    .unwrap();
+    static ref BREAK_RE: Regex = Regex::new(r"^(?<from>\d{4}) *- *(?<to>\d{4})$").unwrap();
+    static ref HOLIDAY_RE: Regex =
+        Regex::new(&format!(r"^(?<date>{DATE_PATTERN})( +(?<name>.+))?$")).unwrap();
+    static ref SCHEDULE_RE: Regex =
+        Regex::new(r"^(?i)(mon|tue|wed|thu|fri|sat|sun)( +(mon|tue|wed|thu|fri|sat|sun))*$").unwrap();
+    static ref CHECK_RE: Regex = Regex::new(
+        r"^(day +over +(?<day>[:0-9]+)|range +over +(?<range>[:0-9]+)|start +before +(?<start>\d{4})|end +after +(?<end>\d{4})|(?<future>future)|(?<empty>empty))( +(?<severity>warning|error))?$"
+    )
+    .unwrap();
+    static ref TEMPLATE_RE: Regex =
+        Regex::new(r"^(?<format>text|markdown|html) *: *(?<path>.+)$").unwrap();
+}
+
+/// Matches projects by client and optionally by code and subcode.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Getters)]
+pub struct ProjectPattern {
+    client: String,
+    code: Option<String>,
+    subcode: Option<String>,
+}
+
+impl ProjectPattern {
+    pub fn new(client: &str, code: Option<&str>, subcode: Option<&str>) -> Self {
+        ProjectPattern {
+            client: client.to_string(),
+            code: code.map(|s| s.to_string()),
+            subcode: subcode.map(|s| s.to_string()),
+        }
+    }
+
+    /// Parses a pattern in the same form as a time line label (e.g., "acme,cms").
+    pub fn parse(text: &str) -> Result<Self> {
+        let caps = PROJECT_PATTERN_RE
+            .captures(text.trim())
+            .ok_or_else(|| ConfigError::InvalidProject(text.to_string()))?;
+        Ok(parse_project_pattern(&caps))
+    }
+
+    pub fn matches(&self, project: &Project) -> bool {
+        self.client == *project.client()
+            && self.code.as_ref().is_none_or(|c| c == project.code())
+            && self.subcode.as_ref().is_none_or(|s| s == project.subcode())
+    }
+
+    /// Number of components in the pattern.  Higher values are more specific.
+    pub fn specificity(&self) -> usize {
+        1 + self.code.iter().count() + self.subcode.iter().count()
+    }
+}
+
+impl Display for ProjectPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.client)?;
+        if let Some(code) = &self.code {
+            write!(f, ",{code}")?;
+        }
+        if let Some(subcode) = &self.subcode {
+            write!(f, ",{subcode}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Optional settings loaded from a file alongside the time log.
+#[derive(Debug, PartialEq, Clone, Default, Getters)]
+pub struct Config {
+    rates: Rates,
+    budgets: Vector<Budget>,
+    aliases: Vector<Alias>,
+    registry: Registry,
+    templates: OrdMap<String, String>,
+    balance: Option<Balance>,
+    gaps: Option<GapRules>,
+    calendar: WorkCalendar,
+    checks: Vector<SanityCheck>,
+    targets: Vector<Target>,
+    retainers: Vector<Retainer>,
+    warnings: Vector<Diagnostic>,
+}
+
+impl Config {
+    /// Warnings and errors from checking the time log against the config.
+    /// Gaps, implausible entries and missing days are only looked for among
+    /// the requested dates.
+    pub fn diagnostics(
+        &self,
+        day_entries: &Vector<DayEntry>,
+        dates: Option<&DateRange>,
+        today: Date,
+    ) -> Result<Vector<Diagnostic>> {
+        let requested = dates.map_or_else(Vector::new, |dates| {
+            day_entries
+                .iter()
+                .filter(|e| dates.contains(e.date()))
+                .cloned()
+                .collect()
+        });
+        self.collect_diagnostics(day_entries, &requested, dates, today)
+    }
+
+    /// Diagnostics for the check command, which looks for gaps and implausible
+    /// entries in the whole time log and for missing days among the requested
+    /// dates.
+    pub fn check_diagnostics(
+        &self,
+        day_entries: &Vector<DayEntry>,
+        dates: &DateRange,
+        today: Date,
+    ) -> Result<Vector<Diagnostic>> {
+        self.collect_diagnostics(day_entries, day_entries, Some(dates), today)
+    }
+
+    fn collect_diagnostics(
+        &self,
+        day_entries: &Vector<DayEntry>,
+        scanned: &Vector<DayEntry>,
+        dates: Option<&DateRange>,
+        today: Date,
+    ) -> Result<Vector<Diagnostic>> {
+        let mut diagnostics = self.warnings.clone();
+        diagnostics.append(registry_diagnostics(&self.registry, day_entries));
+        diagnostics.append(budget_diagnostics(&self.budgets, day_entries, today));
+        diagnostics.append(gap_diagnostics(&self.gaps, scanned));
+        diagnostics.append(sanity_diagnostics(&self.checks, scanned, today));
+        if let Some(dates) = dates {
+            diagnostics.append(recent_missing_day_diagnostics(
+                &self.calendar,
+                dates,
+                day_entries,
+                today,
+            )?);
+        }
+        Ok(diagnostics)
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    pub fn with_rates(self, rates: Rates) -> Self {
+        Config { rates, ..self }
+    }
+
+    pub fn with_registry(self, registry: Registry) -> Self {
+        Config { registry, ..self }
+    }
+
+    pub fn with_targets(self, targets: Vector<Target>) -> Self {
+        Config { targets, ..self }
+    }
+
+    pub fn with_retainers(self, retainers: Vector<Retainer>) -> Self {
+        Config { retainers, ..self }
+    }
+
+    pub fn with_templates(self, templates: OrdMap<String, String>) -> Self {
+        Config { templates, ..self }
+    }
+}
+
+/// Name of the config file used for a given time log file.
+pub fn config_filename(time_filename: &str) -> String {
+    format!("{time_filename}.conf")
+}
+
+fn parse_project_pattern(caps: &Captures) -> ProjectPattern {
+    ProjectPattern::new(
+        &caps["client"],
+        caps.name("code").map(|m| m.as_str()),
+        caps.name("subcode").map(|m| m.as_str()),
+    )
+}
+
+fn parse_optional_date(caps: &Captures, name: &str) -> Result<Option<Date>> {
+    caps.name(name).map(|m| Date::parse(m.as_str())).transpose()
+}
+
+/// Converts a decimal amount (e.g. "150" or "150.5") into cents.
+fn parse_cents(text: &str) -> Result<u64> {
+    let invalid = || ConfigError::InvalidAmount(text.to_string());
+    let caps = AMOUNT_RE.captures(text).ok_or_else(invalid)?;
+    let whole: u64 = caps[1].parse().map_err(|_| invalid())?;
+    let fraction = caps.get(3).map_or("0", |m| m.as_str());
+    let fraction: u64 = format!("{fraction:0<2}").parse()?;
+    whole
+        .checked_mul(100)
+        .and_then(|cents| cents.checked_add(fraction))
+        .ok_or_else(|| invalid().into())
+}
+
+/// Converts hours with optional minutes (e.g. "120" or "7:30") into minutes.
+fn parse_hours(text: &str) -> Result<u32> {
+    let invalid = || ConfigError::InvalidHours(text.to_string());
+    let caps = HOURS_RE.captures(text).ok_or_else(invalid)?;
+    let hours: u32 = caps[1].parse().map_err(|_| invalid())?;
+    let minutes: u32 = caps.get(3).map_or(Ok(0), |m| m.as_str().parse())?;
+    if minutes >= 60 {
+        bail!(invalid());
+    }
+    hours
+        .checked_mul(60)
+        .and_then(|total| total.checked_add(minutes))
+        .ok_or_else(|| invalid().into())
+}
+
+fn parse_optional_dates(caps: &Captures) -> Result<DateRange> {
+    let first = parse_optional_date(caps, "from")?.unwrap_or(Date::min_date());
+    let last = parse_optional_date(caps, "to")?.unwrap_or(Date::max_date());
+    Ok(DateRange::new(first, last))
+}
+
+// Function to parse a rate (e.g., "acme,cms: 150.00 USD from 01/01/2025")
+fn parse_rate(body: &str) -> Result<Rate> {
+    let caps = RATE_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidRate(body.to_string()))?;
+    let effective = parse_optional_date(&caps, "from")?.unwrap_or(Date::min_date());
+    Ok(Rate::new(
+        parse_project_pattern(&caps),
+        parse_cents(&caps["amount"])?,
+        &caps["currency"],
+        effective,
+    ))
+}
+
+// Function to parse a budget (e.g., "acme,cms: 120 from 01/01/2025 to 06/30/2025")
+fn parse_budget(body: &str) -> Result<Budget> {
+    let caps = BUDGET_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidBudget(body.to_string()))?;
+    Ok(Budget::new(
+        parse_project_pattern(&caps),
+        parse_hours(&caps["hours"])?,
+        parse_optional_dates(&caps)?,
+    ))
+}
+
+// Function to parse a weekly target in the same form as a budget (e.g., "acme,cms: 10 from 01/01/2025")
+fn parse_target(body: &str) -> Result<Target> {
+    let caps = BUDGET_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidTarget(body.to_string()))?;
+    Ok(Target::new(
+        parse_project_pattern(&caps),
+        parse_hours(&caps["hours"])?,
+        parse_optional_dates(&caps)?,
+    ))
+}
+
+// Function to parse an alias (e.g., "acme,content => acme,cms from 01/01/2025")
+fn parse_alias(body: &str) -> Result<Alias> {
+    let caps = ALIAS_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidAlias(body.to_string()))?;
+    Alias::new(
+        ProjectPattern::parse(&caps["old"])?,
+        ProjectPattern::parse(&caps["new"])?,
+        parse_optional_dates(&caps)?,
+    )
+}
+
+// Function to parse a client or project (e.g., "acme,cms = Content Management from 01/01/2025 nonbillable")
+fn parse_project_info(body: &str, client_only: bool) -> Result<ProjectInfo> {
+    let invalid = || match client_only {
+        true => ConfigError::InvalidClient(body.to_string()),
+        false => ConfigError::InvalidProject(body.to_string()),
+    };
+    let caps = PROJECT_INFO_RE.captures(body).ok_or_else(invalid)?;
+    let pattern = parse_project_pattern(&caps);
+    if client_only != pattern.code().is_none() {
+        bail!(invalid());
+    }
+    let billable = caps
+        .name("billing")
+        .is_none_or(|m| m.as_str() == "billable");
+    Ok(ProjectInfo::new(
+        pattern,
+        &caps["name"],
+        parse_optional_dates(&caps)?,
+        billable,
+    ))
+}
+
+// Function to parse a flex-time balance (e.g., "from 01/01/2025 overtime 40")
+fn parse_balance(body: &str) -> Result<Balance> {
+    let caps = BALANCE_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidBalance(body.to_string()))?;
+    Ok(Balance::new(
+        Date::parse(&caps["from"])?,
+        caps.name("overtime")
+            .map(|m| parse_hours(m.as_str()))
+            .transpose()?,
+    ))
+}
+
+// Function to parse a monthly retainer (e.g., "acme: 20 rollover 10 from 01/01/2025")
+fn parse_retainer(body: &str) -> Result<Retainer> {
+    let caps = RETAINER_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidRetainer(body.to_string()))?;
+    Ok(Retainer::new(
+        &caps["client"],
+        parse_hours(&caps["hours"])?,
+        caps.name("rollover")
+            .map_or(Ok(0), |m| parse_hours(m.as_str()))?,
+        parse_optional_date(&caps, "from")?,
+    ))
+}
+
+// Function to parse a declared break (e.g., "1200-1300")
+fn parse_break(body: &str) -> Result<TimeRange> {
+    let caps = BREAK_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidBreak(body.to_string()))?;
+    TimeRange::new(Time::parse(&caps["from"])?, Time::parse(&caps["to"])?)
+}
+
+// Function to parse a holiday with an optional name (e.g., "12/25/2025 Christmas")
+fn parse_holiday(body: &str) -> Result<Date> {
+    let caps = HOLIDAY_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidHoliday(body.to_string()))?;
+    Date::parse(&caps["date"])
+}
+
+// Function to parse the days of the week worked (e.g., "Mon Tue Wed Thu")
+fn parse_schedule(body: &str) -> Result<OrdSet<String>> {
+    if !SCHEDULE_RE.is_match(body) {
+        bail!(ConfigError::InvalidSchedule(body.to_string()));
+    }
+    Ok(body.split_whitespace().map(|d| d.to_uppercase()).collect())
+}
+
+// Function to parse a sanity check with an optional severity (e.g., "day over 12 error")
+fn parse_check(body: &str) -> Result<SanityCheck> {
+    let caps = CHECK_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidCheck(body.to_string()))?;
+    let rule = if let Some(m) = caps.name("day") {
+        SanityRule::MaxDayMinutes(parse_hours(m.as_str())?)
+    } else if let Some(m) = caps.name("range") {
+        SanityRule::MaxRangeMinutes(parse_hours(m.as_str())?)
+    } else if let Some(m) = caps.name("start") {
+        SanityRule::StartBefore(Time::parse(m.as_str())?)
+    } else if let Some(m) = caps.name("end") {
+        SanityRule::EndAfter(Time::parse(m.as_str())?)
+    } else if caps.name("future").is_some() {
+        SanityRule::Future
+    } else {
+        SanityRule::Empty
+    };
+    let severity = match caps.name("severity").map(|m| m.as_str()) {
+        Some("error") => Severity::Error,
+        _ => Severity::Warning,
+    };
+    Ok(SanityCheck::new(rule, severity))
+}
+
+// Function to parse an invoice template (e.g., "html: invoice-template.html")
+fn parse_template(body: &str) -> Result<(String, String)> {
+    let caps = TEMPLATE_RE
+        .captures(body)
+        .ok_or_else(|| ConfigError::InvalidTemplate(body.to_string()))?;
+    Ok((caps["format"].to_string(), caps["path"].to_string()))
+}
+
+/// Parses the config file if it exists.  A missing file yields an empty config.
+pub fn load_config(file_path: &str) -> Result<Config> {
+    if !exists(file_path).map_err(|e| ConfigError::OpenFileFailure(file_path.to_string(), e))? {
+        return Ok(Config::default());
+    }
+    let file = File::open(file_path)
+        .map_err(|e| ConfigError::OpenFileFailure(file_path.to_string(), e))?;
+    let lines = io::BufReader::new(file)
+        .lines()
+        .collect::<Result<Vec<String>, io::Error>>()
+        .map_err(ConfigError::ReadFileFailure)?;
+    parse_config(&lines)
+}
+
+fn parse_config(lines: &[String]) -> Result<Config> {
+    let mut rates = Vector::new();
+    let mut budgets = Vector::new();
+    let mut aliases = Vector::new();
+    let mut projects = Vector::new();
+    let mut templates = OrdMap::new();
+    let mut balance = None;
+    let mut gap_threshold = None;
+    let mut breaks = Vector::new();
+    let mut workdays = None;
+    let mut holidays = OrdSet::new();
+    let mut checks = Vector::new();
+    let mut targets = Vector::new();
+    let mut retainers = Vector::<Retainer>::new();
+    let mut break_lines = Vector::new();
+    for (index, raw_line) in lines.iter().enumerate() {
+        let line_num = index as u32 + 1;
+        let line = remove_comments(raw_line).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = || ConfigError::InvalidLine(line_num, line.clone());
+        let caps = CONFIG_LINE_RE.captures(&line).ok_or_else(invalid)?;
+        let body = caps["body"].trim();
+        match &caps["kind"] {
+            "Rate" => rates.push_back(parse_rate(body).with_context(invalid)?),
+            "Budget" => budgets.push_back(parse_budget(body).with_context(invalid)?),
+            "Target" => targets.push_back(parse_target(body).with_context(invalid)?),
+            "Alias" => aliases.push_back(parse_alias(body).with_context(invalid)?),
+            "Client" => projects.push_back(parse_project_info(body, true).with_context(invalid)?),
+            "Project" => projects.push_back(parse_project_info(body, false).with_context(invalid)?),
+            "Template" => {
+                let (format, path) = parse_template(body).with_context(invalid)?;
+                templates.insert(format, path);
+            }
+            "Balance" => {
+                if balance.is_some() {
+                    return Err(ConfigError::DuplicateBalance(body.to_string()))
+                        .with_context(invalid);
+                }
+                balance = Some(parse_balance(body).with_context(invalid)?);
+            }
+            "Gaps" => {
+                if gap_threshold.is_some() {
+                    return Err(ConfigError::DuplicateGaps(body.to_string())).with_context(invalid);
+                }
+                gap_threshold = Some(parse_hours(body).with_context(invalid)?);
+            }
+            "Holiday" => {
+                holidays.insert(parse_holiday(body).with_context(invalid)?);
+            }
+            "Schedule" => {
+                if workdays.is_some() {
+                    return Err(ConfigError::DuplicateSchedule(body.to_string()))
+                        .with_context(invalid);
+                }
+                workdays = Some(parse_schedule(body).with_context(invalid)?);
+            }
+            "Retainer" => {
+                let retainer = parse_retainer(body).with_context(invalid)?;
+                if retainers.iter().any(|r| r.client() == retainer.client()) {
+                    return Err(ConfigError::DuplicateRetainer(body.to_string()))
+                        .with_context(invalid);
+                }
+                retainers.push_back(retainer);
+            }
+            "Check" => checks.push_back(parse_check(body).with_context(invalid)?),
+            "Break" => {
+                breaks.push_back(parse_break(body).with_context(invalid)?);
+                break_lines.push_back(line_num);
+            }
+            _ => bail!(invalid()),
+        }
+    }
+    let warnings = match gap_threshold {
+        Some(_) => Vector::new(),
+        None => break_lines
+            .iter()
+            .map(|n| {
+                let detail = format!("line {n}: Break ignored without a Gaps line");
+                Diagnostic::new(Severity::Warning, "config", None, &detail)
+            })
+            .collect(),
+    };
+    Ok(Config {
+        rates: Rates::new(&rates),
+        budgets,
+        aliases,
+        registry: Registry::new(&projects),
+        templates,
+        balance,
+        gaps: gap_threshold.map(|t| GapRules::new(t, &breaks)),
+        calendar: WorkCalendar::new(
+            &workdays.unwrap_or_else(|| WorkCalendar::default().workdays().clone()),
+            &holidays,
+        ),
+        checks,
+        targets,
+        retainers,
+        warnings,
+    })
+}
@@ -0,0 +1,304 @@
+use super::*;
+use crate::model::fixtures::{date, project_times, time_range};
+
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_project_pattern() {
+    let client = ProjectPattern::new("acme", None, None);
+    let code = ProjectPattern::new("acme", Some("cms"), None);
+    let subcode = ProjectPattern::new("acme", Some("cms"), Some("fix"));
+    let project = Project::new("acme", "cms", "fix");
+    assert!(client.matches(&project));
+    assert!(code.matches(&project));
+    assert!(subcode.matches(&project));
+    assert!(!subcode.matches(&project.without_subcode()));
+    assert!(!code.matches(&Project::new("acme", "web", "")));
+    assert!(!client.matches(&Project::new("bozon", "cms", "")));
+    assert_eq!(1, client.specificity());
+    assert_eq!(3, subcode.specificity());
+    assert_eq!("acme,cms,fix", subcode.to_string());
+    assert_eq!(subcode, ProjectPattern::parse("acme,cms,fix").unwrap());
+    assert_eq!(client, ProjectPattern::parse(" acme ").unwrap());
+    assert!(ProjectPattern::parse("Acme,cms").is_err());
+}
+
+#[test]
+fn test_parse_cents() {
+    assert_eq!(15000, parse_cents("150").unwrap());
+    assert_eq!(15050, parse_cents("150.5").unwrap());
+    assert_eq!(15005, parse_cents("150.05").unwrap());
+    assert!(parse_cents("1.2.3").is_err());
+    assert_eq!(
+        u64::MAX,
+        parse_cents(&format!("{}.15", u64::MAX / 100)).unwrap()
+    );
+    assert!(parse_cents(&format!("{}.16", u64::MAX / 100)).is_err());
+    assert!(parse_cents(&format!("{}", u64::MAX / 100 + 1)).is_err());
+    assert_eq!(
+        "Invalid amount: 99999999999999999999999",
+        parse_cents("99999999999999999999999")
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn test_parse_hours() {
+    assert_eq!(7200, parse_hours("120").unwrap());
+    assert_eq!(450, parse_hours("7:30").unwrap());
+    assert!(parse_hours("7:60").is_err());
+    assert!(parse_hours("7:5").is_err());
+    assert!(parse_hours(&format!("{}", u32::MAX / 60 + 1)).is_err());
+    assert!(parse_hours(&format!("{}:59", u32::MAX / 60)).is_err());
+    assert_eq!(
+        "Invalid hours: 99999999999",
+        parse_hours("99999999999").unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_parse_budget() {
+    let budget = parse_budget("acme,cms: 120 from 01/01/2025 to 06/30/2025").unwrap();
+    assert_eq!(
+        budget,
+        Budget::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            7200,
+            DateRange::new(date(2025, 1, 1), date(2025, 6, 30))
+        )
+    );
+    let budget = parse_budget("acme: 40:30").unwrap();
+    assert_eq!(2430, *budget.minutes());
+    assert_eq!(
+        DateRange::new(Date::min_date(), Date::max_date()),
+        *budget.dates()
+    );
+    assert!(parse_budget("acme: 40 until 01/01/2025").is_err());
+}
+
+#[test]
+fn test_parse_alias() {
+    let alias = parse_alias("acme,content mgmt => acme,cms to 06/30/2025").unwrap();
+    assert_eq!(
+        ProjectPattern::new("acme", Some("content mgmt"), None),
+        *alias.from()
+    );
+    assert_eq!(ProjectPattern::new("acme", Some("cms"), None), *alias.to());
+    assert_eq!(
+        DateRange::new(Date::min_date(), date(2025, 6, 30)),
+        *alias.dates()
+    );
+    assert!(parse_alias("acme,content => acme").is_err());
+    assert!(parse_alias("acme,content acme,cms").is_err());
+}
+
+#[test]
+fn test_parse_project_info() {
+    let info = parse_project_info(
+        "acme,cms = Content Management from 01/01/2025 nonbillable",
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        ProjectPattern::new("acme", Some("cms"), None),
+        *info.pattern()
+    );
+    assert_eq!("Content Management", info.name());
+    assert_eq!(
+        DateRange::new(date(2025, 1, 1), Date::max_date()),
+        *info.dates()
+    );
+    assert!(!info.billable());
+    let info = parse_project_info("acme = Acme Corp", true).unwrap();
+    assert_eq!("Acme Corp", info.name());
+    assert!(info.billable());
+    assert!(parse_project_info("acme,cms = CMS", true).is_err());
+    assert!(parse_project_info("acme = Acme Corp", false).is_err());
+    assert!(parse_project_info("acme,cms", false).is_err());
+}
+
+#[test]
+fn test_parse_rate() {
+    let rate = parse_rate("acme,content mgmt : 150.00 USD from 01/01/2025").unwrap();
+    assert_eq!(
+        rate,
+        Rate::new(
+            ProjectPattern::new("acme", Some("content mgmt"), None),
+            15000,
+            "USD",
+            date(2025, 1, 1)
+        )
+    );
+    let rate = parse_rate("acme: 95 EUR").unwrap();
+    assert_eq!(*rate.effective(), Date::min_date());
+    assert!(parse_rate("acme 95 EUR").is_err());
+}
+
+#[test]
+fn test_parse_config() {
+    let config = parse_config(&lines(
+        "-- billing rates\n\nRate: acme: 100 USD\nRate: acme,cms: 150 USD -- raised\n",
+    ))
+    .unwrap();
+    let rate = config
+        .rates()
+        .rate_for(&Project::new("acme", "cms", ""), &date(2025, 1, 1));
+    assert_eq!(Some(15000), rate.map(|r| *r.cents_per_hour()));
+
+    let config = parse_config(&lines("Template: html: /tmp/invoice.html")).unwrap();
+    assert_eq!(
+        Some(&"/tmp/invoice.html".to_string()),
+        config.templates().get("html")
+    );
+    assert!(parse_config(&lines("Template: pdf: /tmp/invoice.pdf")).is_err());
+
+    let config = parse_config(&lines("Balance: from 01/01/2025 overtime 40:30")).unwrap();
+    assert_eq!(
+        Some(Balance::new(date(2025, 1, 1), Some(40 * 60 + 30))),
+        *config.balance()
+    );
+    let config = parse_config(&lines("Balance: from 01/01/2025")).unwrap();
+    assert_eq!(
+        Some(Balance::new(date(2025, 1, 1), None)),
+        *config.balance()
+    );
+    assert!(parse_config(&lines("Balance: 01/01/2025")).is_err());
+    assert!(parse_config(&lines("Balance: from 01/01/2025\nBalance: from 01/01/2024")).is_err());
+
+    let config = parse_config(&lines("Break: 1200-1300\nGaps: 0:20\nBreak: 1730 - 1745")).unwrap();
+    let gaps = config.gaps().as_ref().unwrap();
+    assert_eq!(20, *gaps.threshold_minutes());
+    assert_eq!(
+        im::vector!("1200-1300".to_string(), "1730-1745".to_string()),
+        gaps.breaks()
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vector<_>>()
+    );
+    let config = parse_config(&lines("Rate: acme: 100 USD\nBreak: 1200-1300")).unwrap();
+    assert_eq!(None, *config.gaps());
+    assert_eq!(
+        "config: line 2: Break ignored without a Gaps line",
+        config.warnings()[0].to_string()
+    );
+    assert!(parse_config(&lines("Break: 1300-1200\nGaps: 0:20")).is_err());
+    let error = parse_config(&lines("Gaps: 0:20\nGaps: 0:30")).unwrap_err();
+    assert_eq!("Invalid config line:2: Gaps: 0:30", error.to_string());
+
+    let config = parse_config(&lines(
+        "Schedule: Mon tue WED thu\nHoliday: 12/25/2025 Christmas\nHoliday: 12/26/2025",
+    ))
+    .unwrap();
+    assert!(config.calendar().is_workday(&date(2025, 12, 22)));
+    assert!(!config.calendar().is_workday(&date(2025, 12, 25)));
+    assert!(!config.calendar().is_workday(&date(2025, 12, 26)));
+    assert!(!config.calendar().is_workday(&date(2026, 1, 2)));
+    assert!(Config::default().calendar().is_workday(&date(2026, 1, 2)));
+    assert!(parse_config(&lines("Schedule: Monday")).is_err());
+    assert!(parse_config(&lines("Schedule: Mon\nSchedule: Tue")).is_err());
+    assert!(parse_config(&lines("Holiday: 12/25")).is_err());
+
+    let config = parse_config(&lines(
+        "Check: day over 12 error\nCheck: range over 6:30\nCheck: start before 0600 warning\nCheck: end after 2200\nCheck: future error\nCheck: empty",
+    ))
+    .unwrap();
+    assert_eq!(
+        im::vector!(
+            SanityCheck::new(SanityRule::MaxDayMinutes(12 * 60), Severity::Error),
+            SanityCheck::new(SanityRule::MaxRangeMinutes(6 * 60 + 30), Severity::Warning),
+            SanityCheck::new(
+                SanityRule::StartBefore(Time::new(6, 0).unwrap()),
+                Severity::Warning
+            ),
+            SanityCheck::new(
+                SanityRule::EndAfter(Time::new(22, 0).unwrap()),
+                Severity::Warning
+            ),
+            SanityCheck::new(SanityRule::Future, Severity::Error),
+            SanityCheck::new(SanityRule::Empty, Severity::Warning)
+        ),
+        *config.checks()
+    );
+    assert!(parse_config(&lines("Check: day over twelve")).is_err());
+    assert!(parse_config(&lines("Check: empty fatal")).is_err());
+
+    let config = parse_config(&lines(
+        "Target: acme,cms: 10 from 07/07/2025\nTarget: bozon: 2:30",
+    ))
+    .unwrap();
+    assert_eq!(
+        im::vector!(
+            Target::new(
+                ProjectPattern::new("acme", Some("cms"), None),
+                600,
+                DateRange::new(date(2025, 7, 7), Date::max_date())
+            ),
+            Target::new(
+                ProjectPattern::new("bozon", None, None),
+                150,
+                DateRange::new(Date::min_date(), Date::max_date())
+            )
+        ),
+        *config.targets()
+    );
+    assert!(parse_config(&lines("Target: acme,cms: ten")).is_err());
+
+    let config = parse_config(&lines(
+        "Retainer: acme: 20 rollover 10 from 01/01/2025\nRetainer: bozon: 7:30",
+    ))
+    .unwrap();
+    assert_eq!(
+        im::vector!(
+            Retainer::new("acme", 1200, 600, Some(date(2025, 1, 1))),
+            Retainer::new("bozon", 450, 0, None)
+        ),
+        *config.retainers()
+    );
+    assert!(parse_config(&lines("Retainer: acme,cms: 20")).is_err());
+    assert!(parse_config(&lines("Retainer: acme: 20\nRetainer: acme: 30")).is_err());
+
+    let error = parse_config(&lines("Rate: acme: 100 USD\nBogus: value")).unwrap_err();
+    assert_eq!("Invalid config line:2: Bogus: value", error.to_string());
+}
+
+#[test]
+fn test_diagnostics_scope() {
+    let cms = Project::new("acme", "cms", "");
+    let gappy = |d: u8, line_number: u32| {
+        DayEntry::new(
+            date(2025, 7, d),
+            &im::vector!(project_times(
+                &cms,
+                im::vector!(time_range(8, 0, 12, 0), time_range(13, 0, 17, 0))
+            )),
+            line_number,
+        )
+    };
+    let day_entries = im::vector!(gappy(7, 1), gappy(8, 4));
+    let config = parse_config(&lines("Gaps: 0:30\nCheck: end after 1600")).unwrap();
+    let lines_of = |kind: &str, diagnostics: &Vector<Diagnostic>| {
+        diagnostics
+            .iter()
+            .filter(|d| d.kind() == kind)
+            .map(|d| d.line_number().unwrap())
+            .collect::<Vec<_>>()
+    };
+    let dates = DateRange::new(date(2025, 7, 8), date(2025, 7, 8));
+    let today = date(2025, 7, 9);
+    let diagnostics = config
+        .diagnostics(&day_entries, Some(&dates), today)
+        .unwrap();
+    assert_eq!(vec![4], lines_of("unaccounted gap", &diagnostics));
+    assert_eq!(vec![4], lines_of("late end", &diagnostics));
+    let diagnostics = config.diagnostics(&day_entries, None, today).unwrap();
+    assert!(lines_of("unaccounted gap", &diagnostics).is_empty());
+    assert!(lines_of("late end", &diagnostics).is_empty());
+    let diagnostics = config
+        .check_diagnostics(&day_entries, &dates, today)
+        .unwrap();
+    assert_eq!(vec![1, 4], lines_of("unaccounted gap", &diagnostics));
+    assert_eq!(vec![1, 4], lines_of("late end", &diagnostics));
+}
@@ -0,0 +1,162 @@
+use crate::model::{DayEntry, ProjectTimes};
+use crate::registry::Registry;
+use anyhow::Result;
+use im::Vector;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt::Display;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+lazy_static! {
+    static ref CONDITION_RE: Regex =
+        Regex::new(r"^(?<field>[a-z]+)(?<op>=|~)(?<value>.+)$").unwrap();
+    static ref TAG_RE: Regex = Regex::new(r"#(?<tag>[-\w]+)").unwrap();
+}
+
+#[derive(Error, Debug)]
+enum FilterError {
+    #[error("Invalid filter condition: '{0}' (expected field=value or project~regex)")]
+    InvalidCondition(String),
+    #[error("Unknown filter field: '{0}' (expected client, project, subcode, tag or billable)")]
+    UnknownField(String),
+    #[error("Invalid billable filter value: '{0}' (expected yes or no)")]
+    InvalidBillable(String),
+    #[error("Invalid project regex: '{0}': {1}")]
+    InvalidRegex(String, regex::Error),
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Client(String),
+    Project(Regex),
+    Subcode(String),
+    Tag(String),
+    Billable(bool),
+}
+
+/// Converts a glob with `*` and `?` wildcards into an anchored regex.
+fn glob_to_regex(glob: &str) -> String {
+    let mut answer = "^".to_string();
+    for c in glob.chars() {
+        match c {
+            '*' => answer.push_str(".*"),
+            '?' => answer.push('.'),
+            _ => answer.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    answer.push('$');
+    answer
+}
+
+fn parse_condition(text: &str) -> Result<Condition> {
+    let caps = CONDITION_RE
+        .captures(text)
+        .ok_or_else(|| FilterError::InvalidCondition(text.to_string()))?;
+    let value = &caps["value"];
+    let condition = match (&caps["field"], &caps["op"]) {
+        ("client", "=") => Condition::Client(value.to_string()),
+        ("project", op) => {
+            let pattern = if op == "=" {
+                glob_to_regex(value)
+            } else {
+                value.to_string()
+            };
+            let regex = Regex::new(&pattern)
+                .map_err(|e| FilterError::InvalidRegex(value.to_string(), e))?;
+            Condition::Project(regex)
+        }
+        ("subcode", "=") => Condition::Subcode(value.to_string()),
+        ("tag", "=") => Condition::Tag(value.trim_start_matches('#').to_string()),
+        ("billable", "=") => match value {
+            "yes" | "true" => Condition::Billable(true),
+            "no" | "false" => Condition::Billable(false),
+            _ => Err(FilterError::InvalidBillable(value.to_string()))?,
+        },
+        ("client" | "subcode" | "tag" | "billable", _) => {
+            Err(FilterError::InvalidCondition(text.to_string()))?
+        }
+        (field, _) => Err(FilterError::UnknownField(field.to_string()))?,
+    };
+    Ok(condition)
+}
+
+/// Tags are words in a note starting with `#`.
+pub fn note_tags(note: &str) -> Vector<String> {
+    TAG_RE
+        .captures_iter(note)
+        .map(|c| c["tag"].to_string())
+        .collect()
+}
+
+/// Conditions limiting the time included in a report.  A project line is kept
+/// only if it matches every condition.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    text: String,
+    conditions: Vector<Condition>,
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl Filter {
+    /// Parses space separated conditions such as `client=acme project=acme,c* tag=meeting`.
+    pub fn parse(text: &str) -> Result<Filter> {
+        let conditions = text
+            .split_whitespace()
+            .map(parse_condition)
+            .collect::<Result<Vector<_>>>()?;
+        Ok(Filter {
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            conditions,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    fn matches(&self, times: &ProjectTimes, registry: &Registry) -> bool {
+        let project = times.project();
+        self.conditions.iter().all(|c| match c {
+            Condition::Client(client) => project.client() == client,
+            Condition::Project(regex) => regex.is_match(&project.format()),
+            Condition::Subcode(subcode) => project.subcode() == subcode,
+            Condition::Tag(tag) => times
+                .note()
+                .as_ref()
+                .is_some_and(|n| note_tags(n).contains(tag)),
+            Condition::Billable(billable) => registry.is_billable(project) == *billable,
+        })
+    }
+
+    /// Keeps only the project lines matching the filter.  Dates left without any
+    /// project lines are dropped.
+    pub fn apply(&self, day_entries: &Vector<DayEntry>, registry: &Registry) -> Vector<DayEntry> {
+        if self.is_empty() {
+            return day_entries.clone();
+        }
+        day_entries
+            .iter()
+            .filter_map(|e| {
+                let projects: Vector<ProjectTimes> = e
+                    .projects()
+                    .iter()
+                    .filter(|p| self.matches(p, registry))
+                    .cloned()
+                    .collect();
+                if projects.is_empty() {
+                    None
+                } else {
+                    Some(DayEntry::new(*e.date(), &projects, *e.line_number()))
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,96 @@
+use super::*;
+use crate::config::ProjectPattern;
+use crate::model::fixtures::{date, project_times, time_range};
+use crate::model::{Date, DateRange, Project};
+use crate::registry::ProjectInfo;
+use im::vector;
+
+fn times(client: &str, code: &str, subcode: &str, note: Option<&str>) -> ProjectTimes {
+    project_times(
+        &Project::new(client, code, subcode),
+        vector!(time_range(8, 0, 9, 0)),
+    )
+    .with_note(note.map(|n| n.to_string()))
+}
+
+fn day_entries() -> Vector<DayEntry> {
+    vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                times("acme", "cms", "", Some("sync #meeting")),
+                times("acme", "cms", "fix", None),
+                times("acme", "internal", "", Some("#admin #meeting"))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(times("bozon", "prototype", "", None)),
+            5
+        )
+    )
+}
+
+fn projects(filter: &str) -> Vec<String> {
+    let registry = Registry::new(&vector!(ProjectInfo::new(
+        ProjectPattern::new("acme", Some("internal"), None),
+        "Internal",
+        DateRange::new(Date::min_date(), Date::max_date()),
+        false
+    )));
+    Filter::parse(filter)
+        .unwrap()
+        .apply(&day_entries(), &registry)
+        .iter()
+        .flat_map(|e| e.projects().iter().map(|p| p.project().format()))
+        .collect()
+}
+
+#[test]
+fn test_glob_to_regex() {
+    assert_eq!(r"^acme,c.*$", glob_to_regex("acme,c*"));
+    assert_eq!(r"^a\.b.$", glob_to_regex("a.b?"));
+}
+
+#[test]
+fn test_note_tags() {
+    assert_eq!(
+        vector!("admin".to_string(), "follow-up".to_string()),
+        note_tags("#admin call #follow-up")
+    );
+    assert!(note_tags("no tags").is_empty());
+}
+
+#[test]
+fn test_apply() {
+    assert_eq!(4, projects("").len());
+    assert_eq!(vec!["bozon,prototype"], projects("client=bozon"));
+    assert_eq!(
+        vec!["acme,cms", "acme,cms,fix"],
+        projects("project=acme,cms*")
+    );
+    assert_eq!(vec!["acme,cms,fix"], projects("project~fix$"));
+    assert_eq!(vec!["acme,cms,fix"], projects("subcode=fix"));
+    assert_eq!(vec!["acme,cms", "acme,internal"], projects("tag=meeting"));
+    assert_eq!(vec!["acme,internal"], projects("tag=#admin"));
+    assert_eq!(vec!["acme,internal"], projects("billable=no"));
+    assert_eq!(
+        vec!["acme,cms"],
+        projects("client=acme  billable=yes tag=meeting")
+    );
+    assert!(projects("client=nobody").is_empty());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Filter::parse("client").is_err());
+    assert!(Filter::parse("color=red").is_err());
+    assert!(Filter::parse("billable=maybe").is_err());
+    assert!(Filter::parse("client~acme").is_err());
+    assert!(Filter::parse("project~(").is_err());
+    assert_eq!(
+        "client=acme tag=x",
+        Filter::parse(" client=acme   tag=x ").unwrap().to_string()
+    );
+}
@@ -0,0 +1,109 @@
+use crate::check::{Diagnostic, Severity};
+use crate::model::{Date, DayEntry, TimeRange, format_hours};
+use derive_getters::Getters;
+use im::Vector;
+
+#[cfg(test)]
+mod tests;
+
+/// Minimum length of unaccounted time between ranges worth reporting along
+/// with the declared breaks that never count towards it.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct GapRules {
+    threshold_minutes: u32,
+    breaks: Vector<TimeRange>,
+}
+
+impl GapRules {
+    pub fn new(threshold_minutes: u32, breaks: &Vector<TimeRange>) -> Self {
+        GapRules {
+            threshold_minutes,
+            breaks: breaks.clone(),
+        }
+    }
+
+    /// Minutes of a gap not covered by any declared break.
+    fn unaccounted_minutes(&self, gap: &TimeRange) -> u32 {
+        let from = gap.from().minute_of_day();
+        let to = gap.to().minute_of_day();
+        (from..to)
+            .filter(|m| {
+                !self
+                    .breaks
+                    .iter()
+                    .any(|b| b.from().minute_of_day() <= *m && *m < b.to().minute_of_day())
+            })
+            .count() as u32
+    }
+}
+
+/// Time between two consecutive ranges of a day that no project accounts for.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Gap {
+    date: Date,
+    line_number: u32,
+    range: TimeRange,
+    unaccounted_minutes: u32,
+}
+
+impl Gap {
+    pub fn diagnostic(&self) -> Diagnostic {
+        let detail = format!(
+            "{} {} ({} not declared as a break)",
+            self.date,
+            self.range,
+            format_hours(self.unaccounted_minutes)
+        );
+        Diagnostic::new(
+            Severity::Warning,
+            "unaccounted gap",
+            Some(self.line_number),
+            &detail,
+        )
+    }
+}
+
+/// Finds the gaps between consecutive ranges across every project of a day
+/// with more unaccounted time than the threshold.
+pub fn find_gaps(rules: &GapRules, entry: &DayEntry) -> Vector<Gap> {
+    let mut ranges: Vec<TimeRange> = entry
+        .projects()
+        .iter()
+        .flat_map(|p| p.time_ranges().iter().copied())
+        .collect();
+    ranges.sort();
+    let mut answer = Vector::new();
+    let mut end = match ranges.first() {
+        Some(first) => *first.to(),
+        None => return answer,
+    };
+    for range in ranges.iter().skip(1) {
+        if let Ok(gap) = TimeRange::new(end, *range.from()) {
+            let unaccounted_minutes = rules.unaccounted_minutes(&gap);
+            if unaccounted_minutes > rules.threshold_minutes {
+                answer.push_back(Gap {
+                    date: *entry.date(),
+                    line_number: *entry.line_number(),
+                    range: gap,
+                    unaccounted_minutes,
+                });
+            }
+        }
+        end = end.max(*range.to());
+    }
+    answer
+}
+
+/// Warnings for every gap in the day entries when gap detection is configured.
+pub fn gap_diagnostics(
+    rules: &Option<GapRules>,
+    day_entries: &Vector<DayEntry>,
+) -> Vector<Diagnostic> {
+    rules.as_ref().map_or_else(Vector::new, |rules| {
+        day_entries
+            .iter()
+            .flat_map(|e| find_gaps(rules, e))
+            .map(|g| g.diagnostic())
+            .collect()
+    })
+}
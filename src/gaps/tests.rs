@@ -0,0 +1,45 @@
+use super::*;
+use crate::model::Project;
+use crate::model::fixtures::{date, project_times, time_range};
+use im::vector;
+
+#[test]
+fn test_find_gaps() {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    let entry = DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times(
+                &cms,
+                vector!(time_range(8, 0, 12, 0), time_range(16, 0, 16, 20))
+            ),
+            project_times(
+                &web,
+                vector!(time_range(13, 30, 15, 0), time_range(15, 10, 15, 50))
+            )
+        ),
+        12,
+    );
+    let rules = GapRules::new(15, &Vector::new());
+    let gaps: Vec<(TimeRange, u32)> = find_gaps(&rules, &entry)
+        .iter()
+        .map(|g| (g.range, g.unaccounted_minutes))
+        .collect();
+    assert_eq!(vec![(time_range(12, 0, 13, 30), 90)], gaps);
+
+    let rules = GapRules::new(15, &vector!(time_range(12, 0, 13, 0)));
+    assert_eq!(
+        vector!(
+            "unaccounted gap:12: 07/07/2025 1200-1330 (0:30 not declared as a break)".to_string()
+        ),
+        gap_diagnostics(&Some(rules), &vector!(entry.clone()))
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vector<_>>()
+    );
+
+    let rules = GapRules::new(15, &vector!(time_range(11, 45, 13, 45)));
+    assert!(find_gaps(&rules, &entry).is_empty());
+    assert!(gap_diagnostics(&None, &vector!(entry)).is_empty());
+}
@@ -0,0 +1,444 @@
+use crate::config::Config;
+use crate::model::{Date, DateRange, DayEntry, ProjectTimes};
+use crate::rates::{Amounts, format_cents};
+use crate::report::{BillingLine, escape_markdown, render_time};
+use anyhow::Result;
+use derive_getters::Getters;
+use im::Vector;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs::{self, File, OpenOptions, exists};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+enum InvoiceError {
+    #[error("Unable to open ledger file {0}: {1}")]
+    OpenLedgerFailure(String, #[source] io::Error),
+    #[error("Unable to read ledger file {0}: {1}")]
+    ReadLedgerFailure(String, #[source] io::Error),
+    #[error("Unable to write ledger file {0}: {1}")]
+    WriteLedgerFailure(String, #[source] io::Error),
+    #[error("Invalid ledger line:{0}: {1}")]
+    InvalidLedgerLine(u32, String),
+    #[error("Unable to read template file {0}: {1}")]
+    ReadTemplateFailure(String, #[source] io::Error),
+}
+
+lazy_static! {
+    static ref LEDGER_LINE_RE: Regex = Regex::new(
+        r"^(?<number>\d+) (?<client>[a-z]+) (?<first>\d{2}/\d{2}/\d{4}) (?<last>\d{2}/\d{2}/\d{4}) (?<issued>\d{2}/\d{2}/\d{4})$"
+    )
+    .unwrap();
+}
+
+const TEXT_TEMPLATE: &str = "INVOICE {{number}}
+
+Client: {{client}}
+Period: {{period}}
+Issued: {{issued}}
+
+{{items}}
+
+TOTAL: {{total}}
+";
+
+const MARKDOWN_TEMPLATE: &str = "# Invoice {{number}}
+
+- Client: {{client}}
+- Period: {{period}}
+- Issued: {{issued}}
+
+| Project | Hours | Rate | Amount |
+|---------|------:|-----:|-------:|
+{{items}}
+| **Total** | | | **{{total}}** |
+";
+
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Invoice {{number}}</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 4px 12px; border-bottom: 1px solid #ccc; text-align: left; }
+td.num, th.num { text-align: right; }
+td.notes { color: #666; font-size: smaller; }
+</style>
+</head>
+<body>
+<h1>Invoice {{number}}</h1>
+<p>Client: {{client}}<br>Period: {{period}}<br>Issued: {{issued}}</p>
+<table>
+<tr><th>Project</th><th class="num">Hours</th><th class="num">Rate</th><th class="num">Amount</th></tr>
+{{items}}
+<tr><th colspan="3">Total</th><th class="num">{{total}}</th></tr>
+</table>
+</body>
+</html>
+"#;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InvoiceFormat {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl InvoiceFormat {
+    pub fn all() -> [InvoiceFormat; 3] {
+        [
+            InvoiceFormat::Text,
+            InvoiceFormat::Markdown,
+            InvoiceFormat::Html,
+        ]
+    }
+
+    /// Name used for the format in `Template:` config lines.
+    pub fn name(&self) -> &'static str {
+        match self {
+            InvoiceFormat::Text => "text",
+            InvoiceFormat::Markdown => "markdown",
+            InvoiceFormat::Html => "html",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            InvoiceFormat::Text => "txt",
+            InvoiceFormat::Markdown => "md",
+            InvoiceFormat::Html => "html",
+        }
+    }
+
+    fn default_template(&self) -> &'static str {
+        match self {
+            InvoiceFormat::Text => TEXT_TEMPLATE,
+            InvoiceFormat::Markdown => MARKDOWN_TEMPLATE,
+            InvoiceFormat::Html => HTML_TEMPLATE,
+        }
+    }
+
+    fn escape(&self, text: &str) -> String {
+        match self {
+            InvoiceFormat::Text => text.to_string(),
+            InvoiceFormat::Markdown => escape_markdown(text),
+            InvoiceFormat::Html => text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Invoice {
+    number: u32,
+    client: String,
+    client_name: String,
+    dates: DateRange,
+    issued: Date,
+    lines: Vector<BillingLine>,
+}
+
+impl Invoice {
+    pub fn new(
+        number: u32,
+        client: &str,
+        dates: DateRange,
+        issued: Date,
+        lines: &Vector<BillingLine>,
+    ) -> Self {
+        Invoice {
+            number,
+            client: client.to_string(),
+            client_name: client.to_string(),
+            dates,
+            issued,
+            lines: lines.clone(),
+        }
+    }
+
+    /// Shows the client under a registered display name rather than its id.
+    pub fn with_client_name(self, client_name: &str) -> Self {
+        Invoice {
+            client_name: client_name.to_string(),
+            ..self
+        }
+    }
+
+    pub fn total(&self) -> Amounts {
+        let mut total = Amounts::new();
+        for line in self.lines.iter() {
+            if let Some(rate) = line.rate() {
+                total.add(rate.currency(), *line.amount_cents());
+            }
+        }
+        total
+    }
+
+    pub fn formatted_number(&self) -> String {
+        format!("{:04}", self.number)
+    }
+
+    /// Name of the invoice file for a format, placed in the directory holding
+    /// the time log file.
+    pub fn filename(&self, format: InvoiceFormat, time_filename: &str) -> String {
+        let name = format!("invoice-{}.{}", self.formatted_number(), format.extension());
+        match Path::new(time_filename).parent() {
+            Some(dir) => dir.join(name).to_string_lossy().to_string(),
+            None => name,
+        }
+    }
+}
+
+/// Restricts day entries to the projects of a single client.  Days with no time
+/// for the client are dropped.
+pub fn client_day_entries(day_entries: &Vector<DayEntry>, client: &str) -> Vector<DayEntry> {
+    day_entries
+        .iter()
+        .map(|e| {
+            let projects: Vector<ProjectTimes> = e
+                .projects()
+                .iter()
+                .filter(|p| p.project().client() == client)
+                .cloned()
+                .collect();
+            DayEntry::new(*e.date(), &projects, *e.line_number())
+        })
+        .filter(|e| !e.projects().is_empty())
+        .collect()
+}
+
+fn line_rate(line: &BillingLine) -> String {
+    line.rate().as_ref().map_or("-".to_string(), |r| {
+        format!(
+            "{}/h",
+            format_cents(*r.cents_per_hour() as i64, r.currency())
+        )
+    })
+}
+
+fn line_amount(line: &BillingLine) -> String {
+    line.rate().as_ref().map_or("-".to_string(), |r| {
+        format_cents(*line.amount_cents(), r.currency())
+    })
+}
+
+fn render_text_items(lines: &Vector<BillingLine>) -> String {
+    let rows: Vec<[String; 4]> = lines
+        .iter()
+        .map(|l| {
+            [
+                l.name().clone(),
+                render_time(*l.billable_minutes(), 1),
+                line_rate(l),
+                line_amount(l),
+            ]
+        })
+        .collect();
+    let header = [
+        "PROJECT".to_string(),
+        "HOURS".to_string(),
+        "RATE".to_string(),
+        "AMOUNT".to_string(),
+    ];
+    let mut widths = [0usize; 4];
+    for row in rows.iter().chain([&header]) {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+    let format_row = |row: &[String; 4]| {
+        format!(
+            "{:w0$}   {:>w1$}   {:>w2$}   {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        )
+    };
+    let mut answer = vec![format_row(&header)];
+    for (row, line) in rows.iter().zip(lines.iter()) {
+        answer.push(format_row(row));
+        for note in line.notes() {
+            answer.push(format!("    {note}"));
+        }
+    }
+    answer.join("\n")
+}
+
+fn render_markdown_items(lines: &Vector<BillingLine>) -> String {
+    let format = InvoiceFormat::Markdown;
+    lines
+        .iter()
+        .map(|l| {
+            let mut project = format.escape(l.name());
+            if !l.notes().is_empty() {
+                let notes: Vec<String> = l.notes().iter().map(|n| format.escape(n)).collect();
+                project = format!("{project}<br>_{}_", notes.join("; "));
+            }
+            format!(
+                "| {} | {} | {} | {} |",
+                project,
+                render_time(*l.billable_minutes(), 1),
+                line_rate(l),
+                line_amount(l)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_html_items(lines: &Vector<BillingLine>) -> String {
+    let format = InvoiceFormat::Html;
+    let mut answer = Vec::new();
+    for l in lines {
+        answer.push(format!(
+            r#"<tr><td>{}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{}</td></tr>"#,
+            format.escape(l.name()),
+            render_time(*l.billable_minutes(), 1),
+            format.escape(&line_rate(l)),
+            format.escape(&line_amount(l))
+        ));
+        if !l.notes().is_empty() {
+            let notes: Vec<String> = l.notes().iter().map(|n| format.escape(n)).collect();
+            answer.push(format!(
+                r#"<tr><td class="notes" colspan="4">{}</td></tr>"#,
+                notes.join("<br>")
+            ));
+        }
+    }
+    answer.join("\n")
+}
+
+/// Loads the template configured for the format or the built-in one if none is configured.
+/// Relative template paths are found in the directory holding the config file.
+pub fn load_template(
+    format: InvoiceFormat,
+    config: &Config,
+    config_filename: &str,
+) -> Result<String> {
+    match config.templates().get(format.name()) {
+        Some(path) => {
+            let dir = Path::new(config_filename).parent().unwrap_or(Path::new(""));
+            let path = dir.join(path);
+            Ok(fs::read_to_string(&path).map_err(|e| {
+                InvoiceError::ReadTemplateFailure(path.to_string_lossy().to_string(), e)
+            })?)
+        }
+        None => Ok(format.default_template().to_string()),
+    }
+}
+
+/// Replaces the `{{name}}` placeholders in the template with invoice values.
+pub fn render_invoice(invoice: &Invoice, format: InvoiceFormat, template: &str) -> String {
+    let items = match format {
+        InvoiceFormat::Text => render_text_items(&invoice.lines),
+        InvoiceFormat::Markdown => render_markdown_items(&invoice.lines),
+        InvoiceFormat::Html => render_html_items(&invoice.lines),
+    };
+    template
+        .replace("{{number}}", &invoice.formatted_number())
+        .replace("{{client}}", &format.escape(&invoice.client_name))
+        .replace(
+            "{{period}}",
+            &format!("{} - {}", invoice.dates.first(), invoice.dates.last()),
+        )
+        .replace("{{issued}}", &invoice.issued.to_string())
+        .replace("{{total}}", &format.escape(&invoice.total().to_string()))
+        .replace("{{items}}", &items)
+}
+
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct LedgerEntry {
+    number: u32,
+    client: String,
+    dates: DateRange,
+    issued: Date,
+}
+
+/// Record of previously issued invoice numbers.
+#[derive(Debug, Clone, PartialEq, Default, Getters)]
+pub struct Ledger {
+    entries: Vector<LedgerEntry>,
+}
+
+impl Ledger {
+    /// Invoice already issued to the client for the same dates.
+    pub fn entry_for(&self, client: &str, dates: &DateRange) -> Option<&LedgerEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.client == client && e.dates == *dates)
+    }
+
+    pub fn next_number(&self) -> u32 {
+        1 + self.entries.iter().map(|e| e.number).max().unwrap_or(0)
+    }
+}
+
+/// Name of the invoice ledger file used for a given time log file.
+pub fn ledger_filename(time_filename: &str) -> String {
+    format!("{time_filename}.invoices")
+}
+
+fn parse_ledger_line(line_num: u32, line: &str) -> Result<LedgerEntry> {
+    let caps = LEDGER_LINE_RE
+        .captures(line)
+        .ok_or_else(|| InvoiceError::InvalidLedgerLine(line_num, line.to_string()))?;
+    Ok(LedgerEntry {
+        number: caps["number"].parse()?,
+        client: caps["client"].to_string(),
+        dates: DateRange::new(Date::parse(&caps["first"])?, Date::parse(&caps["last"])?),
+        issued: Date::parse(&caps["issued"])?,
+    })
+}
+
+/// Parses the ledger file if it exists.  A missing file yields an empty ledger.
+pub fn load_ledger(file_path: &str) -> Result<Ledger> {
+    let open_failure = |e| InvoiceError::OpenLedgerFailure(file_path.to_string(), e);
+    if !exists(file_path).map_err(open_failure)? {
+        return Ok(Ledger::default());
+    }
+    let file = File::open(file_path).map_err(open_failure)?;
+    let mut entries = Vector::new();
+    for (index, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| InvoiceError::ReadLedgerFailure(file_path.to_string(), e))?;
+        if !line.trim().is_empty() {
+            entries.push_back(parse_ledger_line(index as u32 + 1, line.trim())?);
+        }
+    }
+    Ok(Ledger { entries })
+}
+
+/// Appends the invoice number to the ledger file, creating the file if needed.
+pub fn record_invoice(file_path: &str, invoice: &Invoice) -> Result<()> {
+    let write_failure = |e| InvoiceError::WriteLedgerFailure(file_path.to_string(), e);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)
+        .map_err(write_failure)?;
+    writeln!(
+        file,
+        "{} {} {} {} {}",
+        invoice.formatted_number(),
+        invoice.client,
+        invoice.dates.first(),
+        invoice.dates.last(),
+        invoice.issued
+    )
+    .map_err(write_failure)?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use super::*;
+use crate::config::ProjectPattern;
+use crate::model::Project;
+use crate::model::fixtures::{date, time_range};
+use crate::rates::{Rate, Rates};
+use crate::report::compute_billing;
+use im::{OrdMap, vector};
+
+fn project_times(client: &str, code: &str, h1: u16, h2: u16) -> ProjectTimes {
+    ProjectTimes::new(
+        Project::new(client, code, ""),
+        &vector!(time_range(h1, 0, h2, 0)),
+    )
+    .unwrap()
+}
+
+fn test_invoice() -> Invoice {
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 15));
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times("acme", "cms", 8, 10)
+                .with_note(Some("invoice: <b>templates</b> & fixes".to_string())),
+            project_times("acme", "web", 10, 11).with_note(Some("slow client".to_string())),
+            project_times("bozon", "cms", 11, 12)
+        ),
+        1
+    ));
+    let day_entries = client_day_entries(&day_entries, "acme");
+    assert_eq!(2, day_entries[0].projects().len());
+    let config = Config::default().with_rates(Rates::new(&vector!(Rate::new(
+        ProjectPattern::new("acme", Some("cms"), None),
+        15000,
+        "USD",
+        Date::min_date()
+    ))));
+    let lines = compute_billing(dates, &day_entries, &config).unwrap();
+    Invoice::new(3, "acme", dates, date(2025, 7, 16), &lines)
+}
+
+#[test]
+fn test_render_text_invoice() {
+    let invoice = test_invoice();
+    assert_eq!("300.00 USD", invoice.total().to_string());
+    assert_eq!(
+        "invoice-0003.md",
+        invoice.filename(InvoiceFormat::Markdown, "times.txt")
+    );
+    assert_eq!(
+        "logs/invoice-0003.html",
+        invoice.filename(InvoiceFormat::Html, "logs/times.txt")
+    );
+
+    let text = render_invoice(&invoice, InvoiceFormat::Text, TEXT_TEMPLATE);
+    assert_eq!(
+        "INVOICE 0003
+
+Client: acme
+Period: 07/01/2025 - 07/15/2025
+Issued: 07/16/2025
+
+PROJECT    HOURS           RATE       AMOUNT
+acme,cms    2:00   150.00 USD/h   300.00 USD
+    <b>templates</b> & fixes
+acme,web    1:00              -            -
+
+TOTAL: 300.00 USD
+",
+        text
+    );
+}
+
+#[test]
+fn test_render_html_invoice() {
+    let html = render_invoice(&test_invoice(), InvoiceFormat::Html, HTML_TEMPLATE);
+    assert!(html.contains("<title>Invoice 0003</title>"));
+    assert!(html.contains(
+        r#"<td class="notes" colspan="4">&lt;b&gt;templates&lt;/b&gt; &amp; fixes</td>"#
+    ));
+    assert!(!html.contains("slow client"));
+    assert!(html.contains(r#"<th class="num">300.00 USD</th>"#));
+}
+
+#[test]
+fn test_render_markdown_invoice() {
+    let markdown = render_invoice(&test_invoice(), InvoiceFormat::Markdown, MARKDOWN_TEMPLATE);
+    assert!(markdown.contains("| acme,cms<br>_&lt;b&gt;templates&lt;/b&gt; &amp; fixes_ |"));
+    assert!(!markdown.contains("slow client"));
+    assert_eq!(
+        r"fix\_bug \*now\* in \`main\` a\|b",
+        InvoiceFormat::Markdown.escape("fix_bug *now* in `main` a|b")
+    );
+}
+
+#[test]
+fn test_load_template() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("invoice.md"), "# Invoice {{number}}").unwrap();
+    let config_filename = dir.path().join("times.txt.conf");
+    let config_filename = config_filename.to_str().unwrap();
+    let config = Config::default().with_templates(OrdMap::unit(
+        "markdown".to_string(),
+        "invoice.md".to_string(),
+    ));
+    assert_eq!(
+        "# Invoice {{number}}",
+        load_template(InvoiceFormat::Markdown, &config, config_filename).unwrap()
+    );
+    assert_eq!(
+        TEXT_TEMPLATE,
+        load_template(InvoiceFormat::Text, &config, config_filename).unwrap()
+    );
+    assert!(load_template(InvoiceFormat::Markdown, &config, "times.txt.conf").is_err());
+}
+
+#[test]
+fn test_ledger() {
+    let ledger = Ledger::default();
+    assert_eq!(1, ledger.next_number());
+
+    let entry = parse_ledger_line(1, "0007 acme 07/01/2025 07/15/2025 07/16/2025").unwrap();
+    assert_eq!(7, *entry.number());
+    assert!(parse_ledger_line(2, "0007 acme").is_err());
+
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 15));
+    let ledger = Ledger {
+        entries: vector!(entry.clone()),
+    };
+    assert_eq!(8, ledger.next_number());
+    assert_eq!(Some(&entry), ledger.entry_for("acme", &dates));
+    assert_eq!(date(2025, 7, 16), *entry.issued());
+    assert_eq!(None, ledger.entry_for("bozon", &dates));
+}
+
+#[test]
+fn test_record_invoice() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("times.txt.invoices");
+    let path = path.to_str().unwrap();
+    assert_eq!(Ledger::default(), load_ledger(path).unwrap());
+
+    let invoice = test_invoice();
+    record_invoice(path, &invoice).unwrap();
+    let ledger = load_ledger(path).unwrap();
+    let entry = ledger.entry_for("acme", invoice.dates()).unwrap();
+    assert_eq!(3, *entry.number());
+    assert_eq!(invoice.issued(), entry.issued());
+    assert_eq!(4, ledger.next_number());
+}
@@ -1,27 +1,132 @@
 extern crate scopeguard;
 
+mod alias;
 mod append;
+mod balance;
+mod budget;
+mod calendar;
+mod check;
+mod config;
 mod core;
+mod filter;
+mod gaps;
+mod invoice;
 mod model;
 mod parse;
 mod random;
+mod rates;
+mod registry;
+mod rename;
 mod report;
+mod retainer;
+mod sanity;
+mod stats;
+mod target;
 mod watch;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
+use config::Config;
+use config::ProjectPattern;
 use im::Vector;
 use model::{Date, DateRange, DayEntry};
 use std::env;
 use std::env::Args;
+use std::fs;
 
 fn command_append(args: &mut Args) -> Result<()> {
-    let (filename, all_day_entries) = load_file(args)?;
+    let file = load_file(get_filename(args)?, None)?;
+    let all_day_entries = file.all_day_entries;
     let date = Date::today();
     append::validate_date(&all_day_entries, date)?;
 
     let min_date = date.minus_days(30)?;
     let recent_projects = append::recent_projects(&all_day_entries, min_date, 5);
-    append::append_to_file(filename.as_str(), date, &recent_projects)
+    append::append_to_file(file.filename.as_str(), date, &recent_projects)
+}
+
+fn command_check(args: &mut Args) -> Result<()> {
+    let filename = get_filename(args)?;
+    let (format, rest) = take_option(args, "--format")?;
+    let dates = load_dates(&mut rest.into_iter())?();
+    let format = format.unwrap_or("text".to_string());
+    if format != "text" && format != "json" {
+        bail!("command_check: usage: invalid format {format}");
+    }
+
+    let (all_day_entries, mut diagnostics) = parse::check_file(&filename)?;
+    let config = load_config(&filename)?;
+    let all_day_entries = alias::apply_aliases(config.aliases(), &all_day_entries);
+    diagnostics.append(config.check_diagnostics(&all_day_entries, &dates, Date::today())?);
+    let mut sorted: Vec<check::Diagnostic> = diagnostics.into_iter().collect();
+    sorted.sort_by_key(|d| d.line_number().unwrap_or(u32::MAX));
+    let diagnostics: Vector<check::Diagnostic> = sorted.into_iter().collect();
+    if format == "json" {
+        println!("{}", check::render_check_json(&filename, &diagnostics)?);
+    } else {
+        for line in check::render_check_text(&filename, &diagnostics) {
+            println!("{line}");
+        }
+    }
+    let errors = check::error_count(&diagnostics);
+    if errors > 0 {
+        bail!("command_check: {errors} errors found in {filename}");
+    }
+    Ok(())
+}
+
+fn command_invoice(args: &mut Args) -> Result<()> {
+    let filename = get_filename(args)?;
+    let client = args
+        .next()
+        .ok_or_else(|| anyhow!("command_invoice: usage: missing client"))?;
+    let dates = load_dates(args)?();
+    let LoadedFile {
+        filename,
+        all_day_entries,
+        config,
+        ..
+    } = load_file(filename, Some(&dates))?;
+
+    let day_entries = report::day_entries_in_range(&dates, &all_day_entries);
+    let day_entries = invoice::client_day_entries(&day_entries, &client);
+    let lines = report::compute_billing(dates, &day_entries, &config)?;
+    if lines.is_empty() {
+        bail!("command_invoice: no time found for {client} from {dates}");
+    }
+    for line in lines.iter().filter(|l| l.rate().is_none()) {
+        eprintln!("warning: no rate for project {}", line.project().format());
+    }
+
+    let ledger_filename = invoice::ledger_filename(&filename);
+    let ledger = invoice::load_ledger(&ledger_filename)?;
+    let existing = ledger.entry_for(&client, &dates);
+    let (number, issued) = existing.map_or_else(
+        || (ledger.next_number(), Date::today()),
+        |e| (*e.number(), *e.issued()),
+    );
+    let client_name = config.registry().client_name(&client).unwrap_or(&client);
+    let invoice =
+        invoice::Invoice::new(number, &client, dates, issued, &lines).with_client_name(client_name);
+    let config_filename = config::config_filename(&filename);
+    for format in invoice::InvoiceFormat::all() {
+        let template = invoice::load_template(format, &config, &config_filename)?;
+        let output_filename = invoice.filename(format, &filename);
+        fs::write(
+            &output_filename,
+            invoice::render_invoice(&invoice, format, &template),
+        )
+        .with_context(|| format!("command_invoice: writing {output_filename}"))?;
+        println!("Wrote {output_filename}");
+    }
+    if existing.is_none() {
+        invoice::record_invoice(&ledger_filename, &invoice)?;
+    }
+    println!(
+        "Invoice {} for {client}: {}",
+        invoice.formatted_number(),
+        invoice.total()
+    );
+    Ok(())
 }
 
 fn command_random(args: &mut Args) -> Result<()> {
@@ -47,19 +152,104 @@ fn command_random(args: &mut Args) -> Result<()> {
     Ok(())
 }
 
+fn command_rename_project(args: &mut Args) -> Result<()> {
+    let filename = get_filename(args)?;
+    let mut next_pattern = |name: &str| {
+        args.next()
+            .ok_or_else(|| anyhow!("command_rename_project: usage: missing {name} project"))
+            .and_then(|s| ProjectPattern::parse(&s))
+    };
+    let from = next_pattern("old")?;
+    let to = next_pattern("new")?;
+    let first_date = args.next().map(|s| Date::parse(&s)).transpose()?;
+    let last_date = args.next().map(|s| Date::parse(&s)).transpose()?;
+    let dates = DateRange::new(
+        first_date.unwrap_or(Date::min_date()),
+        last_date.unwrap_or(Date::max_date()),
+    );
+
+    let alias = alias::Alias::new(from, to, dates)?;
+    let changed = rename::rename_in_file(&filename, &alias)?;
+    println!("Renamed {changed} lines in {filename}");
+    Ok(())
+}
+
 fn command_report(args: &mut Args) -> Result<()> {
-    let (_, all_day_entries) = load_file(args)?;
-    let dates = load_dates(args)?();
-    println!("Reporting from {} to {}", dates.first(), dates.last());
+    let filename = get_filename(args)?;
+    let (format, rest) = take_option(args, "--format")?;
+    let (layout, rest) = take_option(rest, "--layout")?;
+    let (mode, rest) = take_option(rest, "--mode")?;
+    let (filter, rest) = take_option(rest, "--filter")?;
+    let (compare, rest) = take_option(rest, "--compare")?;
+    let (rounding, rest) = take_option(rest, "--rounding")?;
+    let dates = load_dates(&mut rest.into_iter())?();
+    let LoadedFile {
+        all_day_entries,
+        config,
+        warnings,
+        ..
+    } = load_file(filename, Some(&dates))?;
 
-    let day_entries = report::day_entries_in_range(&dates, &all_day_entries);
-    let lines = report::create_report(dates, &day_entries, report::ReportMode::Detail)?;
+    let format = format.unwrap_or("text".to_string());
+    let renderer = report::renderer_for(&format)
+        .ok_or_else(|| anyhow!("command_report: usage: invalid format {format}"))?;
+    let layout = layout.unwrap_or("weekly".to_string());
+    let layout = report::ReportLayout::from_name(&layout)
+        .ok_or_else(|| anyhow!("command_report: usage: invalid layout {layout}"))?;
+    let mode = mode.unwrap_or("detail".to_string());
+    let mode = report::ReportMode::from_name(&mode)
+        .ok_or_else(|| anyhow!("command_report: usage: invalid mode {mode}"))?;
+    let filter = filter::Filter::parse(&filter.unwrap_or_default())?;
+    if format == "text" {
+        println!("Reporting from {} to {}", dates.first(), dates.last());
+    }
+    let compare = compare
+        .map(|name| {
+            report::ComparisonKind::from_name(&name)
+                .ok_or_else(|| anyhow!("command_report: usage: invalid comparison {name}"))
+        })
+        .transpose()?;
+    let rounding = rounding
+        .map(|name| {
+            report::RoundingPeriod::from_name(&name)
+                .ok_or_else(|| anyhow!("command_report: usage: invalid rounding period {name}"))
+        })
+        .transpose()?;
+    let mut report =
+        report::compute_report(dates, &all_day_entries, mode, layout, &filter, &config)?
+            .with_warnings(&warnings);
+    if let Some(kind) = compare {
+        let comparison =
+            report::compute_comparison(dates, kind, &all_day_entries, mode, &filter, &config)?;
+        report = report.with_comparison(comparison);
+    }
+    if let Some(period) = rounding {
+        let rounding =
+            report::compute_rounding(dates, period, &all_day_entries, mode, &filter, &config)?;
+        report = report.with_rounding(rounding);
+    }
+    let lines = renderer.render(&report)?;
     for line in lines {
         println!("{line}");
     }
     Ok(())
 }
 
+fn command_stats(args: &mut Args) -> Result<()> {
+    let filename = get_filename(args)?;
+    let dates = load_dates(args)?();
+    let LoadedFile {
+        all_day_entries, ..
+    } = load_file(filename, Some(&dates))?;
+
+    println!("Statistics from {} to {}", dates.first(), dates.last());
+    let stats = stats::compute_stats(dates, &all_day_entries)?;
+    for line in stats::render_stats(&stats) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
 fn command_watch(args: &mut Args) -> Result<()> {
     let filename = get_filename(args)?;
     let dates = load_dates(args)?;
@@ -68,14 +258,40 @@ fn command_watch(args: &mut Args) -> Result<()> {
     Ok(())
 }
 
-fn load_file(args: &mut Args) -> Result<(String, Vector<DayEntry>)> {
-    let filename = get_filename(args)?;
+/// Time log file contents along with its config and any warnings found while loading it.
+struct LoadedFile {
+    filename: String,
+    all_day_entries: Vector<DayEntry>,
+    config: Config,
+    warnings: Vector<String>,
+}
 
-    println!("Loading {filename}...");
-    let (all_day_entries, warnings) = parse::parse_file(&filename)?;
+/// Loads the time log and its config, printing any warnings.  Gaps, implausible
+/// entries and missing days are only looked for among the requested dates.
+fn load_file(filename: String, dates: Option<&DateRange>) -> Result<LoadedFile> {
+    eprintln!("Loading {filename}...");
+    let (all_day_entries, mut warnings) = parse::parse_file(&filename)?;
+    eprintln!("Loaded {} dates from {}", all_day_entries.len(), filename);
+    let config = load_config(&filename)?;
+    let all_day_entries = alias::apply_aliases(config.aliases(), &all_day_entries);
+    let diagnostics = config.diagnostics(&all_day_entries, dates, Date::today())?;
+    warnings.extend(diagnostics.iter().map(|d| d.to_string()));
     warnings.iter().for_each(|w| eprintln!("warning: {w}"));
-    println!("Loaded {} dates from {}", all_day_entries.len(), filename);
-    Ok((filename, all_day_entries))
+    Ok(LoadedFile {
+        filename,
+        all_day_entries,
+        config,
+        warnings,
+    })
+}
+
+fn load_config(filename: &str) -> Result<Config> {
+    let config_filename = config::config_filename(filename);
+    let config = config::load_config(&config_filename)?;
+    if config != Config::default() {
+        eprintln!("Loaded config from {config_filename}");
+    }
+    Ok(config)
 }
 
 fn get_filename(args: &mut Args) -> Result<String> {
@@ -85,7 +301,29 @@ fn get_filename(args: &mut Args) -> Result<String> {
     Ok(filename)
 }
 
-fn load_dates(args: &mut Args) -> Result<Box<dyn Fn() -> DateRange>> {
+/// Removes an option and its value (e.g., "--format csv") from the remaining
+/// arguments and returns its value along with the other arguments.
+fn take_option(
+    args: impl IntoIterator<Item = String>,
+    name: &str,
+) -> Result<(Option<String>, Vec<String>)> {
+    let mut args = args.into_iter();
+    let mut value = None;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            let v = args
+                .next()
+                .ok_or_else(|| anyhow!("take_option: usage: missing value for {name}"))?;
+            value = Some(v);
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((value, rest))
+}
+
+fn load_dates(args: &mut impl Iterator<Item = String>) -> Result<Box<dyn Fn() -> DateRange>> {
     let first_date = args.next().map(|s| Date::parse(&s)).transpose()?;
     let last_date = args.next().map(|s| Date::parse(&s)).transpose()?;
     let dates_fn: Box<dyn Fn() -> DateRange> = match (first_date, last_date) {
@@ -104,8 +342,12 @@ fn main() -> Result<()> {
 
     match command.as_str() {
         "append" => command_append(&mut args),
+        "check" => command_check(&mut args),
+        "invoice" => command_invoice(&mut args),
         "random" => command_random(&mut args),
+        "rename-project" => command_rename_project(&mut args),
         "report" => command_report(&mut args),
+        "stats" => command_stats(&mut args),
         "watch" => command_watch(&mut args),
         _ => Err(anyhow!("main: usage: invalid command {}", command.as_str()))?,
     }
@@ -8,6 +8,8 @@ use regex::Regex;
 use std::fmt::Display;
 use thiserror::Error;
 
+#[cfg(test)]
+pub mod fixtures;
 #[cfg(test)]
 mod tests;
 
@@ -32,7 +34,8 @@ lazy_static! {
     static ref DATE_RE: Regex = Regex::new(r"(\d{2})/(\d{2})/(\d{4})").unwrap();
     static ref LONG_MONTHS: HashSet<u8> = hashset!(1, 3, 5, 7, 8, 10, 12);
     static ref SHORT_MONTHS: HashSet<u8> = hashset!(4, 6, 9, 11);
-    static ref DAY_ABBREVS: Vector<String> = vector!(
+    /// Abbreviations of the days of the week starting with Monday.
+    pub static ref DAY_ABBREVS: Vector<String> = vector!(
         "MON".to_string(),
         "TUE".to_string(),
         "WED".to_string(),
@@ -150,8 +153,16 @@ impl Date {
         }
     }
 
+    pub fn max_date() -> Date {
+        Date {
+            year: MAX_YEAR,
+            month: 12,
+            day: 31,
+        }
+    }
+
     pub fn is_monday(&self) -> bool {
-        self.day_num() % 7 == 0
+        self.day_num().is_multiple_of(7)
     }
 
     pub fn is_sunday(&self) -> bool {
@@ -203,7 +214,7 @@ impl Date {
         let days_past = (self.day_num() % 7) as u8;
         let days_offset = 7 - days_past;
         let days_remaining = days_in_month(self.year, self.month) - self.day;
-        if days_offset < days_remaining {
+        if days_offset <= days_remaining {
             Date::new(self.year, self.month, self.day + days_offset)
         } else if self.month < 12 {
             Date::new(self.year, self.month + 1, days_offset - days_remaining)
@@ -282,6 +293,23 @@ impl Date {
         }
     }
 
+    pub fn is_month_end(&self) -> bool {
+        self.day == days_in_month(self.year, self.month)
+    }
+
+    /// Same date one year earlier.  Month ends stay month ends so February
+    /// 28th maps to the 29th in a leap year and the 29th maps to the 28th.
+    pub fn year_earlier(&self) -> Result<Date> {
+        let year = self.year - 1;
+        let last_day = days_in_month(year, self.month);
+        let day = if self.is_month_end() {
+            last_day
+        } else {
+            self.day.min(last_day)
+        };
+        Date::new(year, self.month, day)
+    }
+
     pub(crate) fn minus_days(&self, days: i32) -> Result<Date> {
         let mut d = *self;
         let mut r = days;
@@ -291,6 +319,16 @@ impl Date {
         }
         Ok(d)
     }
+
+    pub(crate) fn plus_days(&self, days: i32) -> Result<Date> {
+        let mut d = *self;
+        let mut r = days;
+        while r > 0 {
+            d = d.next()?;
+            r -= 1;
+        }
+        Ok(d)
+    }
 }
 
 pub struct DateIter {
@@ -430,8 +468,19 @@ impl Project {
         }
     }
 
+    /// Project standing for all of the client's time.
+    pub fn client_only(&self) -> Project {
+        Project {
+            client: self.client.clone(),
+            code: String::new(),
+            subcode: String::new(),
+        }
+    }
+
     pub fn format(&self) -> String {
-        if self.subcode.is_empty() {
+        if self.code.is_empty() {
+            self.client.clone()
+        } else if self.subcode.is_empty() {
             format!("{},{}", self.client, self.code)
         } else {
             format!("{},{},{}", self.client, self.code, self.subcode)
@@ -439,10 +488,14 @@ impl Project {
     }
 }
 
+/// Starts a note that is to be included in invoices.
+const INVOICE_NOTE_PREFIX: &str = "invoice:";
+
 #[derive(Debug, PartialEq, Clone, Getters)]
 pub struct ProjectTimes {
     project: Project,
     time_ranges: Vector<TimeRange>,
+    note: Option<String>,
 }
 
 impl ProjectTimes {
@@ -462,13 +515,48 @@ impl ProjectTimes {
         Ok(ProjectTimes {
             project: project.clone(),
             time_ranges: sorted,
+            note: None,
         })
     }
 
+    pub fn with_project(&self, project: Project) -> Self {
+        ProjectTimes {
+            project,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_note(self, note: Option<String>) -> Self {
+        ProjectTimes { note, ..self }
+    }
+
+    /// The part of the note meant for the client, given only when the note
+    /// starts with `invoice:` so that other comments stay private.
+    pub fn invoice_note(&self) -> Option<String> {
+        self.note
+            .as_ref()
+            .and_then(|n| n.strip_prefix(INVOICE_NOTE_PREFIX))
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+    }
+
+    pub fn total_minutes(&self) -> u32 {
+        self.time_ranges.iter().map(|r| r.duration() as u32).sum()
+    }
+
     pub fn without_subcodes(&self) -> Self {
         ProjectTimes {
             project: self.project.without_subcode(),
             time_ranges: self.time_ranges.clone(),
+            note: self.note.clone(),
+        }
+    }
+
+    pub fn client_only(&self) -> Self {
+        ProjectTimes {
+            project: self.project.client_only(),
+            time_ranges: self.time_ranges.clone(),
+            note: self.note.clone(),
         }
     }
 }
@@ -496,6 +584,19 @@ impl DayEntry {
             ..self.clone()
         }
     }
+
+    pub fn client_only(&self) -> Self {
+        let projects = self.projects.iter().map(|p| p.client_only()).collect();
+        Self {
+            projects,
+            ..self.clone()
+        }
+    }
+}
+
+/// Displays minutes as hours and minutes (e.g., "7:05") for messages.
+pub fn format_hours(minutes: u32) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
 }
 
 fn is_valid_time(hour: u16, minute: u16) -> bool {
@@ -503,7 +604,7 @@ fn is_valid_time(hour: u16, minute: u16) -> bool {
 }
 
 fn is_leap_year(year: u16) -> bool {
-    (year % 4 == 0) && (year % 100 != 0 || year % 400 == 0)
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
 }
 
 fn days_in_month(year: u16, month: u8) -> u8 {
@@ -0,0 +1,19 @@
+//! Values shared by the tests of several modules.
+
+use super::*;
+
+pub fn date(y: u16, m: u8, d: u8) -> Date {
+    Date::new(y, m, d).unwrap()
+}
+
+pub fn time(h: u16, m: u16) -> Time {
+    Time::new(h, m).unwrap()
+}
+
+pub fn time_range(h1: u16, m1: u16, h2: u16, m2: u16) -> TimeRange {
+    TimeRange::new(time(h1, m1), time(h2, m2)).unwrap()
+}
+
+pub fn project_times(project: &Project, time_ranges: Vector<TimeRange>) -> ProjectTimes {
+    ProjectTimes::new(project.clone(), &time_ranges).unwrap()
+}
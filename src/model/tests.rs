@@ -194,6 +194,15 @@ fn test_displays() {
     assert_eq!("[1,2]", ordset_to_string(&ordset!(2, 1)));
 }
 
+#[test]
+fn test_project_format() {
+    let fix = Project::new("acme", "cms", "fix");
+    assert_eq!("acme,cms,fix", fix.format());
+    assert_eq!("acme,cms", fix.without_subcode().format());
+    assert_eq!("acme", fix.client_only().format());
+    assert_eq!(Project::new("acme", "", ""), fix.client_only());
+}
+
 #[test]
 fn test_mondays() {
     assert!(!date(1996, 2, 25).is_monday());
@@ -237,6 +246,7 @@ fn test_mondays() {
     assert_eq!(date(1996, 3, 4), date(1996, 3, 3).next_monday().unwrap());
     assert_eq!(date(1996, 3, 11), date(1996, 3, 4).next_monday().unwrap());
     assert_eq!(date(1997, 1, 6), date(1996, 12, 30).next_monday().unwrap());
+    assert_eq!(date(2025, 3, 31), date(2025, 3, 24).next_monday().unwrap());
 }
 
 #[test]
@@ -276,6 +286,20 @@ fn test_date_next_prev() {
     assert_eq!(date(1996, 3, 1), date(1996, 2, 29).next().unwrap());
     assert_eq!(date(1996, 4, 1), date(1996, 3, 31).next().unwrap());
     assert_eq!(date(1996, 12, 1), date(1996, 11, 30).next().unwrap());
+
+    assert_eq!(date(1997, 1, 2), date(1996, 12, 31).plus_days(2).unwrap());
+    assert_eq!(date(1996, 12, 30), date(1997, 1, 1).minus_days(2).unwrap());
+    assert!(Date::max_date().plus_days(1).is_err());
+}
+
+#[test]
+fn test_year_earlier() {
+    assert_eq!(date(2024, 7, 16), date(2025, 7, 16).year_earlier().unwrap());
+    assert_eq!(date(2024, 2, 29), date(2025, 2, 28).year_earlier().unwrap());
+    assert_eq!(date(2023, 2, 28), date(2024, 2, 29).year_earlier().unwrap());
+    assert_eq!(date(2023, 2, 27), date(2024, 2, 27).year_earlier().unwrap());
+    assert!(date(2024, 2, 29).is_month_end());
+    assert!(!date(2024, 2, 28).is_month_end());
 }
 
 #[test]
@@ -291,3 +315,36 @@ fn test_date_iter() {
     assert_eq!(Some(date(2001, 1, 2)), it.next());
     assert_eq!(None, it.next());
 }
+
+#[test]
+fn test_invoice_note() {
+    let range = TimeRange::new(Time::new(8, 0).unwrap(), Time::new(9, 0).unwrap()).unwrap();
+    let times = ProjectTimes::new(Project::new("acme", "cms", ""), &vector!(range)).unwrap();
+    assert_eq!(
+        None,
+        times
+            .clone()
+            .with_note(Some("slow client".into()))
+            .invoice_note()
+    );
+    assert_eq!(
+        None,
+        times
+            .clone()
+            .with_note(Some("invoice:  ".into()))
+            .invoice_note()
+    );
+    assert_eq!(
+        Some("migrated templates".to_string()),
+        times
+            .with_note(Some("invoice: migrated templates".into()))
+            .invoice_note()
+    );
+}
+
+#[test]
+fn test_format_hours() {
+    assert_eq!("0:00", format_hours(0));
+    assert_eq!("7:05", format_hours(425));
+    assert_eq!("40:30", format_hours(2430));
+}
@@ -1,3 +1,4 @@
+use crate::check::{Diagnostic, Severity};
 use crate::model::{Date, DayEntry, Project, ProjectTimes, Time, TimeRange};
 use anyhow::{Result, bail};
 use im::Vector;
@@ -45,10 +46,14 @@ lazy_static! {
         r"^(?<client>[a-z]+),(?<code>[-/ A-Za-z0-9]+)(,(?<subcode>[-/ A-Za-z0-9]+))? *: *(?<times>.*)$"
     )
     .unwrap();
+    static ref TIME_LINE_LABEL_RE: Regex = Regex::new(
+        r"^(?<client>[a-z]+),(?<code>[-/ A-Za-z0-9]+?)(,(?<subcode>[-/ A-Za-z0-9]+?))?(?<rest> *:.*)$"
+    )
+    .unwrap();
     static ref DATE_LINE_RE: Regex = Regex::new(r"^Date: [A-Za-z]+ (\d{2}/\d{2}/\d{4})$").unwrap();
 }
 
-fn remove_comments(source: &str) -> String {
+pub fn remove_comments(source: &str) -> String {
     let mut current: String = source.to_string();
     loop {
         if let Some(caps) = COMMENT_RE.captures(current.as_str()) {
@@ -63,6 +68,16 @@ fn remove_comments(source: &str) -> String {
     current
 }
 
+/// Text following the first comment marker, kept as a note for time lines
+/// (e.g., "acme,cms: 0800-1200 -- migrated templates").  Notes stay out of
+/// invoices unless they start with `invoice:`.
+fn extract_note(source: &str) -> Option<String> {
+    source
+        .find("--")
+        .map(|i| source[i + 2..].trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
 fn parse_time(hhmm: &str) -> Result<Time> {
     Time::parse(hhmm)
 }
@@ -135,36 +150,120 @@ fn parse_time_line(line: &str) -> Result<(ProjectTimes, bool)> {
     ))
 }
 
-// Function to parse a file into day entries
-pub fn parse_file(file_path: &str) -> Result<(Vector<DayEntry>, Vector<String>)> {
+/// Splits a time line into its project and the text that follows the project label.
+pub fn split_time_line(line: &str) -> Option<(Project, &str)> {
+    let caps = TIME_LINE_LABEL_RE.captures(line)?;
+    let subcode = caps.name("subcode").map_or("", |m| m.as_str());
+    let project = Project::new(&caps["client"], &caps["code"], subcode);
+    Some((project, caps.name("rest")?.as_str()))
+}
+
+fn open_file(file_path: &str) -> Result<io::BufReader<File>> {
     let path = Path::new(file_path);
     let file =
         File::open(path).map_err(|e| ParseError::OpenFileFailure(file_path.to_string(), e))?;
-    let reader = io::BufReader::new(file);
+    Ok(io::BufReader::new(file))
+}
 
+// Function to parse a file into day entries
+pub fn parse_file(file_path: &str) -> Result<(Vector<DayEntry>, Vector<String>)> {
+    let (days, diagnostics) = parse_lines(open_file(file_path)?, true)?;
+    Ok((days, diagnostics.iter().map(|d| d.to_string()).collect()))
+}
+
+/// Parses a file without stopping at invalid lines.  Each invalid line is
+/// reported as an error and skipped.
+pub fn check_file(file_path: &str) -> Result<(Vector<DayEntry>, Vector<Diagnostic>)> {
+    parse_lines(open_file(file_path)?, false)
+}
+
+fn warning(kind: &str, line_num: u32, detail: String) -> Diagnostic {
+    Diagnostic::new(Severity::Warning, kind, Some(line_num), &detail)
+}
+
+/// Errors for ranges on a time line that overlap ranges on earlier time lines
+/// of the same date, even for other projects.
+fn overlap_errors(
+    date: &Date,
+    earlier: &Vector<(u32, ProjectTimes)>,
+    line_num: u32,
+    times: &ProjectTimes,
+) -> Vector<Diagnostic> {
+    let mut answer = Vector::new();
+    for range in times.time_ranges() {
+        for (_, other) in earlier {
+            for other_range in other
+                .time_ranges()
+                .iter()
+                .filter(|r| !TimeRange::distinct(r, range))
+            {
+                answer.push_back(Diagnostic::new(
+                    Severity::Error,
+                    "overlapping times",
+                    Some(line_num),
+                    &format!(
+                        "date='{date}' {range} overlaps {} {other_range}",
+                        other.project().format()
+                    ),
+                ));
+            }
+        }
+    }
+    answer
+}
+
+// Function to parse lines into day entries along with any problems found.
+// When strict the first invalid line is returned as an error.
+fn parse_lines(
+    reader: impl BufRead,
+    strict: bool,
+) -> Result<(Vector<DayEntry>, Vector<Diagnostic>)> {
     let mut days = Vector::new();
     let mut have_date = false;
     let mut date = Date::min_date();
-    let mut projects = Vector::new();
-    let mut warnings = Vector::new();
+    let mut projects = Vector::<(u32, ProjectTimes)>::new();
+    let mut diagnostics = Vector::new();
     let mut line_num = 0;
 
     let mut date_line_num = 0;
+    let day_entry = |date, projects: &Vector<(u32, ProjectTimes)>, line_num| {
+        let projects: Vector<ProjectTimes> = projects.iter().map(|(_, p)| p.clone()).collect();
+        DayEntry::new(date, &projects, line_num)
+    };
     for raw_line in reader.lines() {
         line_num += 1;
-        let line = raw_line
-            .map(|s| remove_comments(&s))
-            .map_err(ParseError::ReadFileFailure)?;
+        let raw_line = raw_line.map_err(ParseError::ReadFileFailure)?;
+        let line = remove_comments(&raw_line);
+        let mut fail = |error: anyhow::Error| -> Result<()> {
+            if strict {
+                return Err(error);
+            }
+            diagnostics.push_back(Diagnostic::new(
+                Severity::Error,
+                "parse error",
+                Some(line_num),
+                &format!("{error:#}"),
+            ));
+            Ok(())
+        };
 
         if is_date_line(line.as_str()) {
-            let new_date = parse_date_line(&line)?;
+            let new_date = match parse_date_line(&line) {
+                Ok(d) => d,
+                Err(e) => {
+                    fail(e)?;
+                    continue;
+                }
+            };
             if have_date {
                 if new_date <= date {
-                    warnings.push_back(format!(
-                        "out of order dates:{line_num}: prev='{date}' new='{new_date}'"
+                    diagnostics.push_back(warning(
+                        "out of order dates",
+                        line_num,
+                        format!("prev='{date}' new='{new_date}'"),
                     ));
                 }
-                days.push_back(DayEntry::new(date, &projects, date_line_num));
+                days.push_back(day_entry(date, &projects, date_line_num));
             } else {
                 have_date = true;
             }
@@ -172,37 +271,46 @@ pub fn parse_file(file_path: &str) -> Result<(Vector<DayEntry>, Vector<String>)>
             date_line_num = line_num;
             projects.clear();
         } else if is_empty_time_line(line.as_str()) {
-            warnings.push_back(format!(
-                "incomplete time line:{line_num}: line: '{}'",
-                line.as_str()
+            diagnostics.push_back(warning(
+                "incomplete time line",
+                line_num,
+                format!("line: '{}'", line.as_str()),
             ));
         } else if is_time_line(line.as_str()) {
             if have_date {
-                let (time_ranges, incomplete) = parse_time_line(&line)?;
+                let (time_ranges, incomplete) = match parse_time_line(&line) {
+                    Ok(answer) => answer,
+                    Err(e) => {
+                        fail(e)?;
+                        continue;
+                    }
+                };
                 if incomplete {
-                    warnings.push_back(format!(
-                        "incomplete time range:{line_num}: date='{}' line='{}'",
-                        &date,
-                        line.as_str()
+                    diagnostics.push_back(warning(
+                        "incomplete time range",
+                        line_num,
+                        format!("date='{}' line='{}'", &date, line.as_str()),
                     ));
                 }
-                projects.push_back(time_ranges);
+                diagnostics.append(overlap_errors(&date, &projects, line_num, &time_ranges));
+                projects.push_back((line_num, time_ranges.with_note(extract_note(&raw_line))));
             } else {
-                bail!(ParseError::TimeLineWithNoDate(line_num, line));
+                fail(ParseError::TimeLineWithNoDate(line_num, line).into())?;
             }
         } else if line == "END" {
             break;
         } else if !line.is_empty() {
-            warnings.push_back(format!(
-                "invalid line:{line_num}: line: '{}'",
-                line.as_str()
+            diagnostics.push_back(warning(
+                "invalid line",
+                line_num,
+                format!("line: '{}'", line.as_str()),
             ));
         }
     }
 
     if have_date {
-        days.push_back(DayEntry::new(date, &projects, date_line_num));
+        days.push_back(day_entry(date, &projects, date_line_num));
     }
 
-    Ok((days, warnings))
+    Ok((days, diagnostics))
 }
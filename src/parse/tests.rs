@@ -31,6 +31,16 @@ fn test_remove_comments() {
     assert_eq!("xyz", remove_comments(" xyz --first  --  second"));
 }
 
+#[test]
+fn test_extract_note() {
+    assert_eq!(None, extract_note("abc,xyz: 0800-1200"));
+    assert_eq!(None, extract_note("abc,xyz: 0800-1200 --  "));
+    assert_eq!(
+        Some("fixed login".to_string()),
+        extract_note("abc,xyz: 0800-1200 -- fixed login")
+    );
+}
+
 #[test]
 fn test_parse_date_line() {
     let line = "Date: Thursday 04/03/2025";
@@ -67,6 +77,19 @@ fn test_parse_time_line_with_subcode() {
     assert_eq!(parse_time_line(line).unwrap().0, expected.unwrap());
 }
 
+#[test]
+fn test_split_time_line() {
+    assert_eq!(
+        Some((Project::new("abc", "x y", ""), ": 0800-1200 -- note")),
+        split_time_line("abc,x y: 0800-1200 -- note")
+    );
+    assert_eq!(
+        Some((Project::new("abc", "def", "xyz"), " :")),
+        split_time_line("abc,def,xyz :")
+    );
+    assert_eq!(None, split_time_line("Date: Thursday 04/03/2025"));
+}
+
 #[test]
 fn test_parse_file() {
     let file_content =
@@ -101,3 +124,66 @@ fn test_parse_file() {
 
     std::fs::remove_file(file_path).unwrap(); // Clean up test file
 }
+
+#[test]
+fn test_parse_file_with_notes() {
+    let file_content = "Date: Thursday 04/03/2025\nabc,xyz: 0800-1200 -- invoice: fixed login\ndef,uvw: 1200-1300 -- lunch meeting\nabc,xyz: 1300-1400\n";
+    let file_path = "test_notes_file.txt";
+    std::fs::write(file_path, file_content).unwrap();
+
+    let (days, _) = parse_file(file_path).unwrap();
+    let notes: Vec<Option<String>> = days[0]
+        .projects()
+        .iter()
+        .map(|p| p.note().clone())
+        .collect();
+    assert_eq!(
+        vec![
+            Some("invoice: fixed login".to_string()),
+            Some("lunch meeting".to_string()),
+            None
+        ],
+        notes
+    );
+    let invoice_notes: Vec<Option<String>> = days[0]
+        .projects()
+        .iter()
+        .map(|p| p.invoice_note())
+        .collect();
+    assert_eq!(
+        vec![Some("fixed login".to_string()), None, None],
+        invoice_notes
+    );
+
+    std::fs::remove_file(file_path).unwrap(); // Clean up test file
+}
+
+#[test]
+fn test_check_file() {
+    let file_content = "Date: Thursday 04/03/2025\nabc,xyz: 0800-1200\ndef,uvw: 1130-1300\nabc,xyz: 2500-2600\nDate: Wednesday 04/02/2025\nabc,xyz: 0800-\n";
+    let file_path = "test_check_file.txt";
+    std::fs::write(file_path, file_content).unwrap();
+
+    let (days, diagnostics) = check_file(file_path).unwrap();
+    assert_eq!(2, days.len());
+    assert_eq!(2, days[0].projects().len());
+    assert_eq!(
+        vec![
+            (Severity::Error, "overlapping times", Some(3)),
+            (Severity::Error, "parse error", Some(4)),
+            (Severity::Warning, "out of order dates", Some(5)),
+            (Severity::Warning, "incomplete time range", Some(6)),
+        ],
+        diagnostics
+            .iter()
+            .map(|d| (*d.severity(), d.kind().as_str(), *d.line_number()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "overlapping times:3: date='04/03/2025' 1130-1300 overlaps abc,xyz 0800-1200",
+        diagnostics[0].to_string()
+    );
+    assert!(parse_file(file_path).is_err());
+
+    std::fs::remove_file(file_path).unwrap(); // Clean up test file
+}
@@ -0,0 +1,111 @@
+use crate::config::ProjectPattern;
+use crate::model::{Date, Project};
+use derive_getters::Getters;
+use im::{OrdMap, Vector};
+use std::fmt::Display;
+
+#[cfg(test)]
+mod tests;
+
+/// Hourly rate for all projects matching a pattern starting on a given date.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Rate {
+    pattern: ProjectPattern,
+    cents_per_hour: u64,
+    currency: String,
+    effective: Date,
+}
+
+impl Rate {
+    pub fn new(
+        pattern: ProjectPattern,
+        cents_per_hour: u64,
+        currency: &str,
+        effective: Date,
+    ) -> Self {
+        Rate {
+            pattern,
+            cents_per_hour,
+            currency: currency.to_string(),
+            effective,
+        }
+    }
+
+    /// Amount earned for the given minutes, rounded to the nearest cent.
+    pub fn amount_cents(&self, minutes: u32) -> i64 {
+        ((minutes as u64 * self.cents_per_hour + 30) / 60) as i64
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Rates {
+    rates: Vector<Rate>,
+}
+
+impl Rates {
+    pub fn new(rates: &Vector<Rate>) -> Self {
+        Rates {
+            rates: rates.clone(),
+        }
+    }
+
+    /// Finds the rate that applies to a project on a date.  The most specific
+    /// matching pattern wins and within that the latest effective date on or
+    /// before the given date.
+    pub fn rate_for(&self, project: &Project, date: &Date) -> Option<&Rate> {
+        self.rates
+            .iter()
+            .filter(|r| r.pattern.matches(project) && r.effective <= *date)
+            .max_by_key(|r| (r.pattern.specificity(), r.effective))
+    }
+}
+
+/// Sums of money in cents keyed by currency.  Amounts in different currencies
+/// are never combined.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Amounts {
+    cents: OrdMap<String, i64>,
+}
+
+impl Amounts {
+    pub fn new() -> Self {
+        Amounts {
+            cents: OrdMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cents.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &i64)> {
+        self.cents.iter()
+    }
+
+    pub fn add(&mut self, currency: &str, cents: i64) {
+        *self.cents.entry(currency.to_string()).or_insert(0) += cents;
+    }
+
+    pub fn add_all(&mut self, other: &Amounts) {
+        other.cents.iter().for_each(|(c, v)| self.add(c, *v));
+    }
+}
+
+/// Displays each currency as a decimal amount (e.g. "1500.00 USD") separated by commas.
+impl Display for Amounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (currency, cents)) in self.cents.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", format_cents(*cents, currency))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn format_cents(cents: i64, currency: &str) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.abs();
+    format!("{}{}.{:02} {}", sign, cents / 100, cents % 100, currency)
+}
@@ -0,0 +1,56 @@
+use super::*;
+use crate::model::fixtures::date;
+
+#[test]
+fn test_rate_for() {
+    let rates = Rates::new(&Vector::from(vec![
+        Rate::new(
+            ProjectPattern::new("acme", None, None),
+            10000,
+            "USD",
+            Date::min_date(),
+        ),
+        Rate::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            15000,
+            "USD",
+            date(2025, 1, 1),
+        ),
+        Rate::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            17500,
+            "USD",
+            date(2025, 7, 1),
+        ),
+    ]));
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    let other = Project::new("bozon", "cms", "");
+    let rate = |p, d| rates.rate_for(p, &d).map(|r| *r.cents_per_hour());
+    assert_eq!(Some(10000), rate(&cms, date(2024, 12, 31)));
+    assert_eq!(Some(15000), rate(&cms, date(2025, 1, 1)));
+    assert_eq!(Some(17500), rate(&cms, date(2025, 7, 2)));
+    assert_eq!(Some(10000), rate(&web, date(2025, 7, 2)));
+    assert_eq!(None, rate(&other, date(2025, 7, 2)));
+}
+
+#[test]
+fn test_amounts() {
+    let rate = Rate::new(
+        ProjectPattern::new("acme", None, None),
+        15000,
+        "USD",
+        Date::min_date(),
+    );
+    assert_eq!(3750, rate.amount_cents(15));
+    assert_eq!(150000, rate.amount_cents(600));
+
+    let mut amounts = Amounts::new();
+    assert_eq!("", amounts.to_string());
+    amounts.add("USD", rate.amount_cents(90));
+    amounts.add("USD", 5);
+    assert_eq!("225.05 USD", amounts.to_string());
+    amounts.add("EUR", 1000);
+    assert_eq!("10.00 EUR, 225.05 USD", amounts.to_string());
+    assert_eq!("-0.50 USD", format_cents(-50, "USD"));
+}
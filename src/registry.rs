@@ -0,0 +1,180 @@
+use crate::check::{Diagnostic, Severity};
+use crate::config::ProjectPattern;
+use crate::model::{Date, DateRange, DayEntry, Project};
+use derive_getters::Getters;
+use im::{OrdSet, Vector};
+
+#[cfg(test)]
+mod tests;
+
+/// Known client or project with the name to show in reports and invoices.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct ProjectInfo {
+    pattern: ProjectPattern,
+    name: String,
+    dates: DateRange,
+    billable: bool,
+}
+
+impl ProjectInfo {
+    pub fn new(pattern: ProjectPattern, name: &str, dates: DateRange, billable: bool) -> Self {
+        ProjectInfo {
+            pattern,
+            name: name.to_string(),
+            dates,
+            billable,
+        }
+    }
+}
+
+/// Registry of known clients and projects.  An empty registry accepts every project.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Registry {
+    entries: Vector<ProjectInfo>,
+}
+
+impl Registry {
+    pub fn new(entries: &Vector<ProjectInfo>) -> Self {
+        Registry {
+            entries: entries.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Most specific entry matching the project.
+    fn lookup(&self, project: &Project) -> Option<&ProjectInfo> {
+        self.entries
+            .iter()
+            .filter(|e| e.pattern.matches(project))
+            .max_by_key(|e| e.pattern.specificity())
+    }
+
+    /// Display name registered for exactly this project (ignoring any
+    /// names registered only for its client).
+    pub fn project_name(&self, project: &Project) -> Option<&str> {
+        let specificity = if project.code().is_empty() {
+            1
+        } else if project.subcode().is_empty() {
+            2
+        } else {
+            3
+        };
+        self.lookup(project)
+            .filter(|e| e.pattern.specificity() == specificity)
+            .map(|e| e.name.as_str())
+    }
+
+    pub fn client_name(&self, client: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.pattern.client() == client && e.pattern.specificity() == 1)
+            .map(|e| e.name.as_str())
+    }
+
+    /// Projects are billable unless their most specific entry says otherwise.
+    pub fn is_billable(&self, project: &Project) -> bool {
+        self.lookup(project).is_none_or(|e| e.billable)
+    }
+
+    fn clients(&self) -> OrdSet<String> {
+        self.entries
+            .iter()
+            .map(|e| e.pattern.client().clone())
+            .collect()
+    }
+
+    fn codes(&self, client: &str) -> OrdSet<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.pattern.client() == client)
+            .filter_map(|e| e.pattern.code().clone())
+            .collect()
+    }
+
+    /// Warning for a project that is not registered or is used outside of
+    /// its active dates.
+    fn check(&self, project: &Project, date: &Date, line_num: u32) -> Option<Diagnostic> {
+        let label = project.format();
+        let warning = |kind: &str, detail: String| {
+            Diagnostic::new(Severity::Warning, kind, Some(line_num), &detail)
+        };
+        let clients = self.clients();
+        if !clients.contains(project.client()) {
+            return Some(warning(
+                "unknown client",
+                format!("'{label}'{}", did_you_mean(project.client(), &clients)),
+            ));
+        }
+        let codes = self.codes(project.client());
+        if !codes.is_empty() && !codes.contains(project.code()) {
+            let suggestion = did_you_mean(project.code(), &codes);
+            return Some(warning("unknown project", format!("'{label}'{suggestion}")));
+        }
+        self.lookup(project)
+            .filter(|e| !e.dates.contains(date))
+            .map(|e| {
+                warning(
+                    "inactive project",
+                    format!("'{label}' used on {date} outside of {}", e.dates),
+                )
+            })
+    }
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push((prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+/// Closest candidate if it is near enough to be a likely typo.
+fn closest<'a>(name: &str, candidates: &'a OrdSet<String>) -> Option<&'a String> {
+    let limit = (name.len() / 3).max(2);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(name, c), c))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+fn did_you_mean(name: &str, candidates: &OrdSet<String>) -> String {
+    closest(name, candidates).map_or(String::new(), |c| format!(" (did you mean '{c}'?)"))
+}
+
+/// Checks every project in the day entries against the registry.  Each project is
+/// reported at most once, on the first date it fails the check.
+pub fn registry_diagnostics(
+    registry: &Registry,
+    day_entries: &Vector<DayEntry>,
+) -> Vector<Diagnostic> {
+    let mut warnings = Vector::new();
+    if registry.is_empty() {
+        return warnings;
+    }
+    let mut reported = OrdSet::<Project>::new();
+    for entry in day_entries {
+        for p in entry.projects() {
+            if reported.contains(p.project()) {
+                continue;
+            }
+            if let Some(w) = registry.check(p.project(), entry.date(), *entry.line_number()) {
+                warnings.push_back(w);
+                reported.insert(p.project().clone());
+            }
+        }
+    }
+    warnings
+}
@@ -0,0 +1,98 @@
+use super::*;
+use crate::model::ProjectTimes;
+use crate::model::fixtures::date;
+use im::vector;
+
+fn registry() -> Registry {
+    let always = DateRange::new(Date::min_date(), Date::max_date());
+    Registry::new(&vector!(
+        ProjectInfo::new(
+            ProjectPattern::new("acme", None, None),
+            "Acme Corporation",
+            always,
+            true
+        ),
+        ProjectInfo::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            "Content Management",
+            DateRange::new(date(2025, 1, 1), date(2025, 6, 30)),
+            true
+        ),
+        ProjectInfo::new(
+            ProjectPattern::new("acme", Some("internal"), None),
+            "Internal",
+            always,
+            false
+        )
+    ))
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(0, edit_distance("acme", "acme"));
+    assert_eq!(1, edit_distance("acme", "acm"));
+    assert_eq!(2, edit_distance("cms", "csm"));
+    assert_eq!(3, edit_distance("", "abc"));
+    assert_eq!(3, edit_distance("kitten", "sitting"));
+}
+
+#[test]
+fn test_names() {
+    let registry = registry();
+    let cms = Project::new("acme", "cms", "");
+    assert_eq!(Some("Content Management"), registry.project_name(&cms));
+    assert_eq!(
+        None,
+        registry.project_name(&Project::new("acme", "cms", "fix"))
+    );
+    assert_eq!(
+        None,
+        registry.project_name(&Project::new("acme", "web", ""))
+    );
+    assert_eq!(
+        Some("Acme Corporation"),
+        registry.project_name(&cms.client_only())
+    );
+    assert_eq!(Some("Acme Corporation"), registry.client_name("acme"));
+    assert_eq!(None, registry.client_name("bozon"));
+    assert!(registry.is_billable(&cms));
+    assert!(!registry.is_billable(&Project::new("acme", "internal", "x")));
+    assert!(Registry::default().is_billable(&cms));
+}
+
+#[test]
+fn test_registry_warnings() {
+    let times =
+        |client, code| ProjectTimes::new(Project::new(client, code, ""), &vector!()).unwrap();
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 6, 30),
+            &vector!(times("acme", "cms"), times("acm", "cms")),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 1),
+            &vector!(
+                times("acme", "cms"),
+                times("acme", "csm"),
+                times("bozon", "web"),
+                times("acm", "cms")
+            ),
+            5
+        )
+    );
+    assert_eq!(
+        vector!(
+            "unknown client:1: 'acm,cms' (did you mean 'acme'?)".to_string(),
+            "inactive project:5: 'acme,cms' used on 07/01/2025 outside of 01/01/2025-06/30/2025"
+                .to_string(),
+            "unknown project:5: 'acme,csm' (did you mean 'cms'?)".to_string(),
+            "unknown client:5: 'bozon,web'".to_string()
+        ),
+        registry_diagnostics(&registry(), &day_entries)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vector<_>>()
+    );
+    assert!(registry_diagnostics(&Registry::default(), &day_entries).is_empty());
+}
@@ -0,0 +1,79 @@
+use crate::alias::Alias;
+use crate::core::{create_temp_file, delete_file};
+use crate::model::Date;
+use crate::parse::{split_time_line, try_parse_date_line};
+use anyhow::Result;
+use scopeguard::defer;
+use std::fs::File;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::{fs, io};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+enum RenameError {
+    #[error("Error opening file {0}: {1}")]
+    OpenFile(String, #[source] io::Error),
+    #[error("Error creating temp file {0}: {1}")]
+    CreateFile(String, #[source] io::Error),
+    #[error("Error writing to file {0}: {1}")]
+    WriteFailed(String, #[source] io::Error),
+    #[error("Error reading from file {0}: {1}")]
+    ReadFailed(String, #[source] io::Error),
+    #[error("Error renaming file from {0} to {1}: {2}")]
+    RenameFile(String, String, #[source] io::Error),
+}
+
+/// Applies the alias to the project line if it is within the alias date range.
+fn rename_line(line: &str, date: Option<Date>, alias: &Alias) -> Option<String> {
+    let date = date?;
+    let (project, rest) = split_time_line(line)?;
+    let renamed = alias.rename(&project, &date)?;
+    Some(format!("{}{}", renamed.format(), rest))
+}
+
+/// Rewrites project lines in the file matched by the alias.  Returns the number
+/// of lines changed.
+pub fn rename_in_file(filename: &str, alias: &Alias) -> Result<usize> {
+    let temp_file = create_temp_file(filename)?;
+    defer! { delete_file(&temp_file).unwrap_or(())}
+
+    let input_file = File::open(Path::new(filename))
+        .map_err(|e| RenameError::OpenFile(filename.to_string(), e))?;
+    let reader = io::BufReader::new(input_file);
+
+    let output_file = File::create(Path::new(&temp_file))
+        .map_err(|e| RenameError::CreateFile(filename.to_string(), e))?;
+    let mut writer = io::BufWriter::new(output_file);
+
+    let mut date = None;
+    let mut ended = false;
+    let mut changed = 0;
+    for raw_line in reader.lines() {
+        let mut line = raw_line.map_err(|e| RenameError::ReadFailed(filename.to_string(), e))?;
+        let trimmed = line.trim();
+        if trimmed == "END" {
+            ended = true;
+        } else if let Some(d) = try_parse_date_line(trimmed) {
+            date = Some(d);
+        } else if !ended && let Some(renamed) = rename_line(&line, date, alias) {
+            if renamed != line {
+                changed += 1;
+            }
+            line = renamed;
+        }
+        writer
+            .write_all(line.as_bytes())
+            .and_then(|_| writer.write_all("\n".as_bytes()))
+            .map_err(|e| RenameError::WriteFailed(temp_file.to_string(), e))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| RenameError::WriteFailed(temp_file.to_string(), e))?;
+    fs::rename(&temp_file, filename)
+        .map_err(|e| RenameError::RenameFile(temp_file.to_string(), filename.to_string(), e))?;
+    Ok(changed)
+}
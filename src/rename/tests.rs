@@ -0,0 +1,107 @@
+use super::*;
+use crate::config::ProjectPattern;
+use crate::model::DateRange;
+use crate::model::fixtures::date;
+
+fn alias(from: ProjectPattern, to: ProjectPattern, dates: DateRange) -> Alias {
+    Alias::new(from, to, dates).unwrap()
+}
+
+fn cms_to_web(dates: DateRange) -> Alias {
+    alias(
+        ProjectPattern::new("acme", Some("cms"), None),
+        ProjectPattern::new("acme", Some("web"), None),
+        dates,
+    )
+}
+
+fn all_dates() -> DateRange {
+    DateRange::new(Date::min_date(), Date::max_date())
+}
+
+#[test]
+fn test_rename_line() {
+    let alias = cms_to_web(DateRange::new(date(2025, 7, 1), date(2025, 7, 31)));
+    let today = Some(date(2025, 7, 7));
+    assert_eq!(
+        Some("acme,web: 0800-1200".to_string()),
+        rename_line("acme,cms: 0800-1200", today, &alias)
+    );
+    assert_eq!(None, rename_line("acme,cms: 0800-1200", None, &alias));
+    assert_eq!(
+        None,
+        rename_line("acme,cms: 0800-1200", Some(date(2025, 6, 30)), &alias)
+    );
+    assert_eq!(
+        None,
+        rename_line("acme,cms: 0800-1200", Some(date(2025, 8, 1)), &alias)
+    );
+    assert_eq!(
+        Some("acme,web: 0800-1200 -- acme,cms migration".to_string()),
+        rename_line("acme,cms: 0800-1200 -- acme,cms migration", today, &alias)
+    );
+    assert_eq!(
+        Some("acme,web,fix: 0800-1200".to_string()),
+        rename_line("acme,cms,fix: 0800-1200", today, &alias)
+    );
+    assert_eq!(None, rename_line("acme,cmsx: 0800-1200", today, &alias));
+    assert_eq!(None, rename_line("acme,cm: 0800-1200", today, &alias));
+    assert_eq!(None, rename_line("-- acme,cms: 0800-1200", today, &alias));
+}
+
+#[test]
+fn test_rename_subcode() {
+    let alias = alias(
+        ProjectPattern::new("acme", Some("cms"), Some("fix")),
+        ProjectPattern::new("acme", Some("cms"), Some("bug")),
+        all_dates(),
+    );
+    let today = Some(date(2025, 7, 7));
+    assert_eq!(
+        Some("acme,cms,bug: 0800-1200".to_string()),
+        rename_line("acme,cms,fix: 0800-1200", today, &alias)
+    );
+    assert_eq!(None, rename_line("acme,cms: 0800-1200", today, &alias));
+    assert_eq!(
+        None,
+        rename_line("acme,cms,fixes: 0800-1200", today, &alias)
+    );
+}
+
+#[test]
+fn test_rename_in_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("times.txt");
+    let path = path.to_str().unwrap();
+    fs::write(
+        path,
+        "Date: Monday 06/30/2025
+acme,cms: 0800-1200
+Date: Monday 07/07/2025
+acme,cms: 0800-1200 -- acme,cms release
+acme,cms,fix: 1300-1400
+acme,cmsx: 1400-1500
+END
+Date: Tuesday 07/08/2025
+acme,cms: 0800-1200
+",
+    )
+    .unwrap();
+    let alias = cms_to_web(DateRange::new(date(2025, 7, 1), date(2025, 7, 31)));
+    assert_eq!(2, rename_in_file(path, &alias).unwrap());
+    assert_eq!(
+        "Date: Monday 06/30/2025
+acme,cms: 0800-1200
+Date: Monday 07/07/2025
+acme,web: 0800-1200 -- acme,cms release
+acme,web,fix: 1300-1400
+acme,cmsx: 1400-1500
+END
+Date: Tuesday 07/08/2025
+acme,cms: 0800-1200
+",
+        fs::read_to_string(path).unwrap()
+    );
+    // Only the renamed log is left behind.
+    assert_eq!(1, fs::read_dir(dir.path()).unwrap().count());
+}
@@ -1,20 +1,125 @@
 use anyhow::{Result, anyhow};
-use im::{HashMap, OrdSet, Vector};
+use derive_getters::Getters;
+use im::{HashMap, OrdMap, OrdSet, Vector};
 use model::{Date, DateRange, DayEntry, Project};
 
+use crate::balance::{BalanceStatus, compute_balance_status};
+use crate::budget::{BudgetStatus, compute_budget_statuses};
+use crate::calendar::WorkCalendar;
+use crate::config::Config;
+use crate::filter::Filter;
 use crate::model::{self, ProjectTimes};
+use crate::rates::{Amounts, Rate, Rates};
+use crate::registry::Registry;
+use crate::retainer::{RetainerLedger, compute_retainer_ledger};
+use crate::target::{Target, TargetStatus, target_for};
+
+mod compare;
+mod csv;
+mod daily;
+mod heatmap;
+mod html;
+mod json;
+mod markdown;
+mod period;
+mod rounding;
+mod table;
+#[cfg(test)]
+mod tests;
+mod text;
+mod timeline;
+
+use compare::Comparison;
+pub use compare::{ComparisonKind, compute_comparison};
+use csv::{CsvLayout, CsvRenderer};
+use daily::DailyListing;
+use heatmap::Heatmap;
+use html::HtmlRenderer;
+use json::JsonRenderer;
+use markdown::MarkdownRenderer;
+pub use markdown::escape_markdown;
+use period::PeriodReport;
+use rounding::RoundingReport;
+pub use rounding::{RoundingPeriod, compute_rounding};
+use text::TextRenderer;
+use timeline::Timeline;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ReportMode {
     Detail,
     Summary,
+    /// All projects of a client are combined into one row.
+    Client,
 }
 
 impl ReportMode {
-    pub fn toggle(&self) -> Self {
+    /// Mode for a name accepted by the `--mode` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "detail" => Some(ReportMode::Detail),
+            "summary" => Some(ReportMode::Summary),
+            "client" => Some(ReportMode::Client),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
         match self {
             ReportMode::Detail => ReportMode::Summary,
-            ReportMode::Summary => ReportMode::Detail,
+            ReportMode::Summary => ReportMode::Client,
+            ReportMode::Client => ReportMode::Detail,
+        }
+    }
+}
+
+/// How time is laid out in the report grids: days within each week, weeks
+/// within each month or months within each year.  The timeline layout instead
+/// draws the hours of each day as a bar showing the project worked, the
+/// daily layout lists every time range worked on each day and the heatmap
+/// layout shows the minutes worked by day of the week and hour of the day.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ReportLayout {
+    Weekly,
+    Monthly,
+    Yearly,
+    Timeline,
+    Daily,
+    Heatmap,
+}
+
+impl ReportLayout {
+    /// Layout for a name accepted by the `--layout` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "weekly" => Some(ReportLayout::Weekly),
+            "monthly" => Some(ReportLayout::Monthly),
+            "yearly" => Some(ReportLayout::Yearly),
+            "timeline" => Some(ReportLayout::Timeline),
+            "daily" => Some(ReportLayout::Daily),
+            "heatmap" => Some(ReportLayout::Heatmap),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportLayout::Weekly => "weekly",
+            ReportLayout::Monthly => "monthly",
+            ReportLayout::Yearly => "yearly",
+            ReportLayout::Timeline => "timeline",
+            ReportLayout::Daily => "daily",
+            ReportLayout::Heatmap => "heatmap",
+        }
+    }
+
+    /// Next layout in the watch command's layout cycle, which leaves out the
+    /// timeline and heatmap since watch draws them in their own tabs.
+    pub fn next(&self) -> Self {
+        match self {
+            ReportLayout::Weekly => ReportLayout::Monthly,
+            ReportLayout::Monthly => ReportLayout::Yearly,
+            ReportLayout::Yearly | ReportLayout::Timeline => ReportLayout::Daily,
+            ReportLayout::Daily | ReportLayout::Heatmap => ReportLayout::Weekly,
         }
     }
 }
@@ -40,27 +145,23 @@ impl Key {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct WeekData {
     minutes: HashMap<Key, u32>,
+    billable: HashMap<Key, u32>,
 }
 
 impl WeekData {
-    fn new() -> WeekData {
-        WeekData {
-            minutes: HashMap::new(),
-        }
-    }
-
     fn clear(&mut self) {
         self.minutes.clear();
+        self.billable.clear();
     }
 
-    fn add_day_entry(&mut self, day_entry: &DayEntry) {
+    fn add_day_entry(&mut self, day_entry: &DayEntry, billable: &BillableMinutes) {
         let day_name = day_entry.date().day_abbrev();
         day_entry.projects().iter().for_each(|p| {
             let key = Key::from_project_times(p, &day_name);
-            let total = p.time_ranges().iter().map(|r| r.duration() as u32).sum();
+            let total = p.total_minutes();
             match self.minutes.get_mut(&key) {
                 Some(m) => *m += total,
                 None => {
@@ -68,6 +169,13 @@ impl WeekData {
                 }
             };
         });
+        for project in entry_projects(day_entry) {
+            let minutes = recorded_billable(billable, &project, day_entry.date());
+            *self
+                .billable
+                .entry(Key::new(&project, &day_name))
+                .or_default() += minutes;
+        }
     }
 
     fn project_day_total(&self, project: &Project, day_name: &str) -> u32 {
@@ -75,48 +183,117 @@ impl WeekData {
         self.minutes.get(key).copied().unwrap_or(0)
     }
 
+    fn project_day_billable(&self, project: &Project, day_name: &str) -> u32 {
+        self.billable(&Key::new(project, day_name))
+    }
+
+    fn billable(&self, key: &Key) -> u32 {
+        billable_minutes(self.billable.get(key).copied().unwrap_or(0))
+    }
+
     fn project_billable(&self, project: &Project) -> u32 {
-        self.compute_total(|k| k.project == *project, |m| billable_minutes(*m))
+        self.compute_total(|k| k.project == *project, |k, _| self.billable(k))
     }
 
     fn project_total(&self, project: &Project) -> u32 {
-        self.compute_total(|k| k.project == *project, |m| *m)
+        self.compute_total(|k| k.project == *project, |_, m| *m)
     }
 
     fn day_billable(&self, day_name: &str) -> u32 {
-        self.compute_total(|k| k.day_name == day_name, |m| billable_minutes(*m))
+        self.compute_total(|k| k.day_name == day_name, |k, _| self.billable(k))
     }
 
     fn day_total(&self, day_name: &str) -> u32 {
-        self.compute_total(|k| k.day_name == day_name, |m| *m)
+        self.compute_total(|k| k.day_name == day_name, |_, m| *m)
     }
 
     fn week_total(&self) -> u32 {
-        self.compute_total(|_| true, |m| *m)
+        self.compute_total(|_| true, |_, m| *m)
     }
 
     fn week_billable(&self) -> u32 {
-        self.compute_total(|_| true, |m| billable_minutes(*m))
+        self.compute_total(|_| true, |k, _| self.billable(k))
     }
 
     fn compute_total<F, M>(&self, filter: F, mapper: M) -> u32
     where
         F: Fn(&Key) -> bool,
-        M: Fn(&u32) -> u32,
+        M: Fn(&Key, &u32) -> u32,
     {
         self.minutes
             .iter()
             .filter(|(k, _)| filter(k))
-            .map(|(_, v)| v)
-            .map(mapper)
+            .map(|(k, v)| mapper(k, v))
             .sum()
     }
 }
 
-fn billable_minutes(m: u32) -> u32 {
+pub fn billable_minutes(m: u32) -> u32 {
     m - (m % 15)
 }
 
+/// Billable minutes for the projects included on one date.  Time is summed per
+/// project before rounding as it is for billing.
+pub fn day_billable_minutes<F>(entry: &DayEntry, include: F) -> u32
+where
+    F: Fn(&Project) -> bool,
+{
+    let mut minutes = OrdMap::<Project, u32>::new();
+    for p in entry.projects().iter().filter(|p| include(p.project())) {
+        *minutes.entry(p.project().clone()).or_default() += p.total_minutes();
+    }
+    minutes.values().map(|m| billable_minutes(*m)).sum()
+}
+
+/// Billable minutes recorded for each project and date with projects adjusted
+/// for the report mode.  Billability is decided on the recorded project so a
+/// summary or client row only counts the time of its billable projects.
+type BillableMinutes = OrdMap<(Project, Date), u32>;
+
+fn compute_billable(
+    day_entries: &Vector<DayEntry>,
+    mode: ReportMode,
+    registry: &Registry,
+) -> BillableMinutes {
+    let mut answer = BillableMinutes::new();
+    for entry in day_entries {
+        for p in entry.projects() {
+            if registry.is_billable(p.project()) {
+                let project = adjust_project_for_mode(p.project(), mode);
+                *answer.entry((project, *entry.date())).or_default() += p.total_minutes();
+            }
+        }
+    }
+    answer
+}
+
+/// Unrounded billable minutes for a project on one date.
+fn recorded_billable(billable: &BillableMinutes, project: &Project, date: &Date) -> u32 {
+    billable
+        .get(&(project.clone(), *date))
+        .copied()
+        .unwrap_or(0)
+}
+
+fn entry_projects(entry: &DayEntry) -> OrdSet<Project> {
+    entry
+        .projects()
+        .iter()
+        .map(|p| p.project().clone())
+        .collect()
+}
+
+/// Total and billable minutes for one date.
+fn day_totals(entry: &DayEntry, billable: &BillableMinutes) -> Minutes {
+    Minutes {
+        minutes: entry.projects().iter().map(|p| p.total_minutes()).sum(),
+        billable: entry_projects(entry)
+            .iter()
+            .map(|p| billable_minutes(recorded_billable(billable, p, entry.date())))
+            .sum(),
+    }
+}
+
 fn unique_projects(day_entries: &Vector<DayEntry>) -> OrdSet<Project> {
     day_entries
         .iter()
@@ -131,16 +308,40 @@ struct ReportData {
     dates: DateRange,
     totals: WeekData,
     weekdays: usize,
+    billing: Vector<BillingLine>,
+    amounts: OrdMap<Project, Amounts>,
+    budgets: Vector<BudgetStatus>,
+    balance: Option<BalanceStatus>,
+    targets: Vector<Target>,
+    calendar: WorkCalendar,
+    retainers: Vector<RetainerLedger>,
+    today: Date,
+    registry: Registry,
+    entries: Vector<DayEntry>,
+    periods: Vector<PeriodReport>,
+    timeline: Timeline,
+    daily: Vector<DailyListing>,
+    heatmap: Heatmap,
 }
 
+impl ReportData {
+    /// Eight hours for each weekday up to the last date with time recorded.
+    fn expected_minutes(&self) -> u32 {
+        480 * self.weekdays as u32
+    }
+}
+
+/// Creates a text report for the day entries within the date range.
 pub fn create_report(
     dates: DateRange,
     day_entries: &Vector<DayEntry>,
     mode: ReportMode,
+    layout: ReportLayout,
+    filter: &Filter,
+    config: &Config,
 ) -> Result<Vector<String>> {
-    let data = compute_report_data(dates, day_entries, mode)?;
-    let lines = render_report_data(&data)?;
-    Ok(lines)
+    let report = compute_report(dates, day_entries, mode, layout, filter, config)?;
+    TextRenderer.render(&report)
 }
 
 pub fn day_entries_in_range(dates: &DateRange, day_entries: &Vector<DayEntry>) -> Vector<DayEntry> {
@@ -156,27 +357,176 @@ pub fn day_entries_in_range(dates: &DateRange, day_entries: &Vector<DayEntry>) -
 fn adjust_day_entry_for_mode(day_entry: &DayEntry, mode: ReportMode) -> DayEntry {
     match mode {
         ReportMode::Summary => day_entry.without_subcodes(),
+        ReportMode::Client => day_entry.client_only(),
         ReportMode::Detail => day_entry.clone(),
     }
 }
 
+fn adjust_project_for_mode(project: &Project, mode: ReportMode) -> Project {
+    match mode {
+        ReportMode::Summary => project.without_subcode(),
+        ReportMode::Client => project.client_only(),
+        ReportMode::Detail => project.clone(),
+    }
+}
+
+/// Billable time and amount for one project at a single rate.  Time without a
+/// configured rate is collected in a line with no rate and a zero amount.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct BillingLine {
+    project: Project,
+    name: String,
+    rate: Option<Rate>,
+    billable_minutes: u32,
+    amount_cents: i64,
+    notes: Vector<String>,
+}
+
+/// Computes billing lines for each project.  Billable minutes are computed per
+/// project and date so that each day is charged at the rate in effect on that day.
+/// Rates are looked up using the full project so subcode rates apply in every mode.
+/// Projects registered as not billable are left out.
+fn compute_billing_lines(
+    day_entries: &Vector<DayEntry>,
+    report_mode: ReportMode,
+    rates: &Rates,
+    registry: &Registry,
+) -> Vector<BillingLine> {
+    let mut minutes = OrdMap::<(Date, Project), (u32, Vector<String>)>::new();
+    for entry in day_entries {
+        for p in entry.projects() {
+            if !registry.is_billable(p.project()) {
+                continue;
+            }
+            let key = (*entry.date(), p.project().clone());
+            let (total, notes) = minutes.entry(key).or_default();
+            *total += p.total_minutes();
+            notes.extend(p.invoice_note());
+        }
+    }
+    let mut lines = Vector::<BillingLine>::new();
+    for ((date, project), (m, notes)) in minutes {
+        let rate = rates.rate_for(&project, &date).cloned();
+        let project = adjust_project_for_mode(&project, report_mode);
+        let billable = billable_minutes(m);
+        let amount = rate.as_ref().map_or(0, |r| r.amount_cents(billable));
+        let line = match lines
+            .iter_mut()
+            .find(|l| l.project == project && l.rate == rate)
+        {
+            Some(line) => line,
+            None => {
+                let name = registry
+                    .project_name(&project)
+                    .map_or_else(|| project.format(), |n| n.to_string());
+                lines.push_back(BillingLine {
+                    project,
+                    name,
+                    rate,
+                    billable_minutes: 0,
+                    amount_cents: 0,
+                    notes: Vector::new(),
+                });
+                lines.back_mut().unwrap()
+            }
+        };
+        line.billable_minutes += billable;
+        line.amount_cents += amount;
+        for note in notes {
+            if !line.notes.contains(&note) {
+                line.notes.push_back(note);
+            }
+        }
+    }
+    lines.sort_by(|a, b| a.project.cmp(&b.project));
+    lines
+}
+
+fn compute_project_amounts(billing: &Vector<BillingLine>) -> OrdMap<Project, Amounts> {
+    let mut amounts = OrdMap::<Project, Amounts>::new();
+    for line in billing {
+        if let Some(rate) = &line.rate {
+            amounts
+                .entry(line.project.clone())
+                .or_default()
+                .add(rate.currency(), line.amount_cents);
+        }
+    }
+    amounts
+}
+
+/// Computes billing lines for the day entries within the date range.
+pub fn compute_billing(
+    dates: DateRange,
+    day_entries: &Vector<DayEntry>,
+    config: &Config,
+) -> Result<Vector<BillingLine>> {
+    let report = compute_report(
+        dates,
+        day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        config,
+    )?;
+    Ok(report.billing)
+}
+
 fn compute_report_data(
     dates: DateRange,
     day_entries: &Vector<DayEntry>,
     report_mode: ReportMode,
+    layout: ReportLayout,
+    filter: &Filter,
+    config: &Config,
 ) -> Result<ReportData> {
+    let budgets = compute_budget_statuses(config.budgets(), day_entries, *dates.last());
+    let balance = config
+        .balance()
+        .as_ref()
+        .map(|b| compute_balance_status(b, day_entries, config.registry(), *dates.last()));
+    let retainers = config
+        .retainers()
+        .iter()
+        .map(|r| {
+            compute_retainer_ledger(r, day_entries, config.registry(), *dates.last())
+                .map(|ledger| ledger.within(&dates))
+        })
+        .collect::<Result<Vector<RetainerLedger>>>()?;
+    let entries = filter.apply(
+        &day_entries_in_range(&dates, day_entries),
+        config.registry(),
+    );
+    let day_entries = &entries;
+    let billing =
+        compute_billing_lines(day_entries, report_mode, config.rates(), config.registry());
+    let amounts = compute_project_amounts(&billing);
+    let billable = compute_billable(day_entries, report_mode, config.registry());
     let day_entries = &day_entries
         .iter()
-        .filter(|e| dates.contains(e.date()))
         .map(|e| adjust_day_entry_for_mode(e, report_mode))
         .collect::<Vector<DayEntry>>();
+    let projects = unique_projects(day_entries);
+    let periods = period::compute_periods(layout, &dates, day_entries, &billable);
+    let timeline = match layout {
+        ReportLayout::Timeline => timeline::compute_timeline(day_entries, &billable),
+        _ => Timeline::default(),
+    };
+    let daily = match layout {
+        ReportLayout::Daily => daily::compute_daily(day_entries, &billable),
+        _ => Vector::new(),
+    };
+    let heatmap = match layout {
+        ReportLayout::Heatmap => heatmap::compute_heatmap(day_entries),
+        _ => Heatmap::default(),
+    };
     let mut weeks = HashMap::<u32, WeekData>::new();
     let week_nums = dates.iter().map(|d| d.week_num()).collect::<OrdSet<u32>>();
     for w in week_nums {
-        weeks.insert(w, WeekData::new());
+        weeks.insert(w, WeekData::default());
     }
-    let mut totals = WeekData::new();
-    let mut current_data = WeekData::new();
+    let mut totals = WeekData::default();
+    let mut current_data = WeekData::default();
     let mut current_week = dates.first().week_num();
 
     for entry in day_entries {
@@ -186,13 +536,12 @@ fn compute_report_data(
             current_data.clear();
             current_week = entry_week;
         };
-        totals.add_day_entry(entry);
-        current_data.add_day_entry(entry);
+        totals.add_day_entry(entry, &billable);
+        current_data.add_day_entry(entry, &billable);
     }
 
     weeks.insert(current_week, current_data);
 
-    let projects = unique_projects(day_entries);
     let last_date = day_entries.last().map(|e| *e.date());
     let weekdays = last_date
         .map(|ld| {
@@ -209,60 +558,42 @@ fn compute_report_data(
         projects,
         dates,
         weekdays,
+        billing,
+        amounts,
+        budgets,
+        balance,
+        targets: config.targets().clone(),
+        calendar: config.calendar().clone(),
+        retainers,
+        today: Date::today(),
+        registry: config.registry().clone(),
+        entries,
+        periods,
+        timeline,
+        daily,
+        heatmap,
     })
 }
 
-fn render_project_label(project: &Project) -> String {
-    if project.subcode().is_empty() {
-        format!("{},{}", project.client(), project.code())
-    } else {
-        format!(
-            "{},{},{}",
-            project.client(),
-            project.code(),
-            project.subcode()
-        )
+fn client_amounts(
+    amounts: &OrdMap<Project, Amounts>,
+    registry: &Registry,
+) -> OrdMap<String, Amounts> {
+    let mut answer = OrdMap::<String, Amounts>::new();
+    for (p, a) in amounts {
+        let client = registry.client_name(p.client()).unwrap_or(p.client());
+        answer.entry(client.to_string()).or_default().add_all(a);
     }
+    answer
 }
 
-fn create_project_labels(projects: &OrdSet<Project>) -> Vector<String> {
-    let mut labels: Vector<String> = projects.iter().map(render_project_label).collect();
-    let width = 4 + labels.iter().map(|label| label.len()).max().unwrap_or(0);
-    labels.push_front("PROJECT".to_string());
-    labels.push_front("".to_string());
-    labels.push_back("TOTALS".to_string());
-    labels.push_back("REPORT".to_string());
-    labels.iter().map(|s| format!("{s:<width$}")).collect()
-}
-
-fn create_day_labels() -> String {
-    "     MON     TUE     WED     THU     FRI     SAT     SUN".to_string()
-}
-
-const COLUMN_PAD: usize = 3;
-
-fn render_dates_line(monday: Date) -> Result<String> {
-    let mut line = "".to_string();
-    let mut d = monday;
-    loop {
-        line += format!(
-            "{:pad$}{:02}/{:02}",
-            "",
-            d.month(),
-            d.day(),
-            pad = COLUMN_PAD
-        )
-        .as_ref();
-        if d.is_sunday() {
-            break;
-        }
-        d = d.next()?;
-    }
-    line += "   TOTALS  REPORT";
-    Ok(line)
+fn total_amounts(amounts: &OrdMap<Project, Amounts>) -> Amounts {
+    let mut answer = Amounts::new();
+    amounts.values().for_each(|a| answer.add_all(a));
+    answer
 }
 
-fn render_time(minutes: u32, hour_len: usize) -> String {
+pub fn render_time(minutes: u32, hour_len: usize) -> String {
     if minutes == 0 {
         format!("{:>width$}", "-", width = hour_len + 3)
     } else {
@@ -290,178 +621,326 @@ fn render_delta(delta_minutes: i32, hour_len: usize) -> String {
     }
 }
 
-fn render_times_line(monday: Date, project: &Project, week_data: &WeekData) -> Result<String> {
-    let mut line = "".to_string();
-    let mut d = monday;
-    loop {
-        let minutes = week_data.project_day_total(project, &d.day_abbrev());
-        let time = render_time(minutes, 2);
-        line += format!("{:pad$}{}", "", time, pad = COLUMN_PAD).as_ref();
-        if d.is_sunday() {
-            break;
+/// Minutes recorded and the billable minutes they round down to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Getters)]
+pub struct Minutes {
+    minutes: u32,
+    billable: u32,
+}
+
+/// Totals for a single date.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct DayReport {
+    date: Date,
+    totals: Minutes,
+}
+
+/// Time for one project in one week with the minutes for each day starting on Monday
+/// and its progress towards a weekly target if one is configured.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ProjectWeek {
+    project: Project,
+    days: Vector<Minutes>,
+    totals: Minutes,
+    target: Option<TargetStatus>,
+}
+
+/// Time for every project in one week.  Weeks always run from Monday to Sunday
+/// even if the report starts or ends mid-week.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct WeekReport {
+    monday: Date,
+    days: Vector<DayReport>,
+    projects: Vector<ProjectWeek>,
+    totals: Minutes,
+}
+
+/// Time and amount earned for one project over the whole report.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ProjectReport {
+    project: Project,
+    name: Option<String>,
+    totals: Minutes,
+    amounts: Option<Amounts>,
+}
+
+impl ProjectReport {
+    /// Display name or the full project label.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.project.format())
+    }
+
+    /// Display name or the client and code without any subcode.
+    pub fn short_label(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.project.without_subcode().format())
+    }
+}
+
+/// Time and amount earned for all projects of one client over the whole report.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ClientReport {
+    client: String,
+    name: Option<String>,
+    totals: Minutes,
+    amounts: Option<Amounts>,
+}
+
+impl ClientReport {
+    /// Display name or the client id.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.client.clone())
+    }
+}
+
+/// Everything computed for a report independent of how it is rendered.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Report {
+    dates: DateRange,
+    mode: ReportMode,
+    layout: ReportLayout,
+    weeks: Vector<WeekReport>,
+    periods: Vector<PeriodReport>,
+    timeline: Timeline,
+    daily: Vector<DailyListing>,
+    heatmap: Heatmap,
+    projects: Vector<ProjectReport>,
+    clients: Vector<ClientReport>,
+    totals: Minutes,
+    expected_minutes: u32,
+    client_amounts: OrdMap<String, Amounts>,
+    total_amounts: Amounts,
+    billing: Vector<BillingLine>,
+    budgets: Vector<BudgetStatus>,
+    retainers: Vector<RetainerLedger>,
+    balance: Option<BalanceStatus>,
+    entries: Vector<DayEntry>,
+    warnings: Vector<String>,
+    comparison: Option<Comparison>,
+    rounding: Option<RoundingReport>,
+}
+
+impl Report {
+    /// Billable minutes less expected minutes.
+    pub fn delta_minutes(&self) -> i32 {
+        self.totals.billable as i32 - self.expected_minutes as i32
+    }
+
+    /// Label of a project in the report or the full project label if it is not found.
+    pub fn project_label(&self, project: &Project) -> String {
+        self.projects
+            .iter()
+            .find(|p| p.project == *project)
+            .map_or_else(|| project.format(), |p| p.label())
+    }
+
+    /// Position of a project in the report's project list.
+    pub fn project_index(&self, project: &Project) -> Option<usize> {
+        self.projects.iter().position(|p| p.project == *project)
+    }
+
+    /// True when any project has a weekly target in any week of the report.
+    pub fn has_targets(&self) -> bool {
+        self.weeks
+            .iter()
+            .flat_map(|w| w.projects.iter())
+            .any(|p| p.target.is_some())
+    }
+
+    pub fn has_amounts(&self) -> bool {
+        self.projects.iter().any(|p| p.amounts.is_some())
+    }
+
+    /// Adds warnings found while loading the time log for renderers that include them.
+    pub fn with_warnings(self, warnings: &Vector<String>) -> Self {
+        Report {
+            warnings: warnings.clone(),
+            ..self
         }
-        d = d.next()?;
-    }
-    let total_time = render_time(week_data.project_total(project), 3);
-    let total_billable = render_time(week_data.project_billable(project), 3);
-    line += format!(
-        "{:pad$}{}  {}",
-        "",
-        total_time,
-        total_billable,
-        pad = COLUMN_PAD
-    )
-    .as_ref();
-    Ok(line)
-}
-
-fn render_totals_line(monday: Date, week_data: &WeekData) -> Result<String> {
-    let mut line = "".to_string();
-    let mut d = monday;
-    loop {
-        let minutes = week_data.day_total(&d.day_abbrev());
-        let time = render_time(minutes, 2);
-        line += format!("{:pad$}{}", "", time, pad = COLUMN_PAD).as_ref();
-        if d.is_sunday() {
-            break;
+    }
+
+    /// Adds the time in earlier dates for renderers to show alongside the report.
+    pub fn with_comparison(self, comparison: Comparison) -> Self {
+        Report {
+            comparison: Some(comparison),
+            ..self
         }
-        d = d.next()?;
-    }
-    let total_minutes = week_data.week_total();
-    let total_time = render_time(total_minutes, 3);
-    line += format!("{:pad$}{}", "", total_time, pad = COLUMN_PAD).as_ref();
-    Ok(line)
-}
-
-fn render_billables_line(monday: Date, week_data: &WeekData) -> Result<String> {
-    let mut line = "".to_string();
-    let mut d = monday;
-    loop {
-        let minutes = week_data.day_billable(&d.day_abbrev());
-        let time = render_time(minutes, 2);
-        line += format!("{:pad$}{}", "", time, pad = COLUMN_PAD).as_ref();
-        if d.is_sunday() {
-            break;
+    }
+
+    /// Adds the time lost or gained to rounding for renderers to show after the report.
+    pub fn with_rounding(self, rounding: RoundingReport) -> Self {
+        Report {
+            rounding: Some(rounding),
+            ..self
         }
-        d = d.next()?;
     }
-    let total_minutes = week_data.week_billable();
-    let total_time = render_time(total_minutes, 3);
-    line += format!("{:pad$}{}", "", total_time, pad = COLUMN_PAD).as_ref();
-    Ok(line)
 }
 
-fn render_grand_totals(
-    projects: &OrdSet<Project>,
-    totals_data: &WeekData,
-    expected_time: u32,
-) -> Vector<String> {
-    let mut answer = Vector::new();
-    let label_width = 3 + projects
-        .iter()
-        .map(|p| p.client().len() + p.code().len())
-        .max()
-        .unwrap_or(0);
-    answer.push_back("".to_string());
-    answer.push_back("".to_string());
-    answer.push_back(format!(
-        "{:lw$}{:pad$}{}{:pad$}{}",
-        "PROJECT",
-        "",
-        "TOTALS",
-        "",
-        "REPORT",
-        lw = label_width,
-        pad = COLUMN_PAD
-    ));
-    for p in projects {
-        answer.push_back(format!(
-            "{:lw$}{:pad$}{:6}{:pad$}{:6}",
-            format!("{},{}", p.client(), p.code()),
-            "",
-            render_time(totals_data.project_total(p), 3),
-            "",
-            render_time(totals_data.project_billable(p), 3),
-            lw = label_width,
-            pad = COLUMN_PAD,
-        ));
-    }
-    answer.push_back(format!(
-        "{:lw$}{:pad$}{}",
-        "TOTALS",
-        "",
-        render_time(totals_data.week_total(), 3),
-        lw = label_width,
-        pad = COLUMN_PAD
-    ));
-    answer.push_back(format!(
-        "{:lw$}{:pad$}{}",
-        "REPORT",
-        "",
-        render_time(totals_data.week_billable(), 3),
-        lw = label_width,
-        pad = COLUMN_PAD
-    ));
-    let delta: i32 = (totals_data.week_billable() as i32) - (expected_time as i32);
-    answer.push_back(format!(
-        "{:lw$}{:pad$}{}",
-        "DELTA",
-        "",
-        render_delta(delta, 3),
-        lw = label_width,
-        pad = COLUMN_PAD
-    ));
-    answer
+/// Converts a report into lines of output in some format.
+pub trait ReportRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>>;
+}
+
+/// Renderer for a format name accepted by the `--format` option.
+pub fn renderer_for(format: &str) -> Option<Box<dyn ReportRenderer>> {
+    match format {
+        "text" => Some(Box::new(TextRenderer)),
+        "markdown" => Some(Box::new(MarkdownRenderer)),
+        "html" => Some(Box::new(HtmlRenderer)),
+        "csv" => Some(Box::new(CsvRenderer::new(CsvLayout::Daily))),
+        "csv-weekly" => Some(Box::new(CsvRenderer::new(CsvLayout::Weekly))),
+        "json" => Some(Box::new(JsonRenderer)),
+        _ => None,
+    }
 }
 
-fn render_report_data(report_data: &ReportData) -> Result<Vector<String>> {
-    let mut answer = Vector::new();
+fn minutes(week_data: &WeekData, project: &Project, day_name: &str) -> Minutes {
+    Minutes {
+        minutes: week_data.project_day_total(project, day_name),
+        billable: week_data.project_day_billable(project, day_name),
+    }
+}
 
-    let left_labels = create_project_labels(&report_data.projects);
-    let full_range = report_data.dates.as_full_weeks()?;
-    for d in full_range {
-        if !d.is_monday() {
-            continue;
+fn build_week(data: &ReportData, monday: Date) -> Result<WeekReport> {
+    let week_data = data
+        .weeks
+        .get(&monday.week_num())
+        .ok_or_else(|| anyhow!("build_week: unable to find week data!"))?;
+    let dates = DateRange::new(monday, monday.this_sunday()?);
+    let days = dates
+        .iter()
+        .map(|d| DayReport {
+            date: d,
+            totals: Minutes {
+                minutes: week_data.day_total(&d.day_abbrev()),
+                billable: week_data.day_billable(&d.day_abbrev()),
+            },
+        })
+        .collect();
+    let projects = data
+        .projects
+        .iter()
+        .map(|p| ProjectWeek {
+            project: p.clone(),
+            days: dates
+                .iter()
+                .map(|d| minutes(week_data, p, &d.day_abbrev()))
+                .collect(),
+            totals: Minutes {
+                minutes: week_data.project_total(p),
+                billable: week_data.project_billable(p),
+            },
+            target: target_for(&data.targets, p, &dates).map(|t| {
+                TargetStatus::new(
+                    *t.minutes(),
+                    week_data.project_total(p),
+                    &dates,
+                    &data.dates,
+                    &data.today,
+                    &data.calendar,
+                )
+            }),
+        })
+        .collect();
+    Ok(WeekReport {
+        monday,
+        days,
+        projects,
+        totals: Minutes {
+            minutes: week_data.week_total(),
+            billable: week_data.week_billable(),
+        },
+    })
+}
+
+/// Sums the project totals and amounts for each client.
+fn build_clients(projects: &Vector<ProjectReport>, registry: &Registry) -> Vector<ClientReport> {
+    let mut clients = Vector::<ClientReport>::new();
+    for p in projects {
+        let client = p.project.client();
+        if clients.back().is_none_or(|c| c.client != *client) {
+            clients.push_back(ClientReport {
+                client: client.to_string(),
+                name: registry.client_name(client).map(|n| n.to_string()),
+                totals: Minutes::default(),
+                amounts: None,
+            });
         }
-        if !answer.is_empty() {
-            answer.push_back("".to_string());
-            answer.push_back("".to_string());
+        let c = clients.back_mut().unwrap();
+        c.totals.minutes += p.totals.minutes;
+        c.totals.billable += p.totals.billable;
+        if let Some(amounts) = &p.amounts {
+            c.amounts.get_or_insert_with(Amounts::new).add_all(amounts);
         }
-        let mut i = 0;
-        answer.push_back(format!("{}{}", left_labels[i], create_day_labels()));
-        i += 1;
-        answer.push_back(format!("{}{}", left_labels[i], render_dates_line(d)?));
-        let week_data = if let Some(w) = report_data.weeks.get(&d.week_num()) {
-            w
-        } else {
-            return Err(anyhow!("render_report_data: unable to find week data!"));
-        };
-        for p in report_data.projects.iter() {
-            i += 1;
-            answer.push_back(format!(
-                "{}{}",
-                left_labels[i],
-                render_times_line(d, p, week_data)?
-            ));
+    }
+    clients
+}
+
+fn build_report(data: ReportData, mode: ReportMode, layout: ReportLayout) -> Result<Report> {
+    let mut weeks = Vector::new();
+    for d in data.dates.as_full_weeks()? {
+        if d.is_monday() {
+            weeks.push_back(build_week(&data, d)?);
         }
-        i += 1;
-        answer.push_back(format!(
-            "{}{}",
-            left_labels[i],
-            render_totals_line(d, week_data)?
-        ));
-        i += 1;
-        answer.push_back(format!(
-            "{}{}",
-            left_labels[i],
-            render_billables_line(d, week_data)?
-        ));
-    }
-    let expected_time = 480 * report_data.weekdays;
-    answer.append(render_grand_totals(
-        &report_data.projects,
-        &report_data.totals,
-        expected_time as u32,
-    ));
-    Ok(answer)
+    }
+    let projects = data
+        .projects
+        .iter()
+        .map(|p| ProjectReport {
+            project: p.clone(),
+            name: data.registry.project_name(p).map(|n| n.to_string()),
+            totals: Minutes {
+                minutes: data.totals.project_total(p),
+                billable: data.totals.project_billable(p),
+            },
+            amounts: data.amounts.get(p).cloned(),
+        })
+        .collect();
+    let clients = build_clients(&projects, &data.registry);
+    Ok(Report {
+        dates: data.dates,
+        mode,
+        layout,
+        weeks,
+        projects,
+        clients,
+        totals: Minutes {
+            minutes: data.totals.week_total(),
+            billable: data.totals.week_billable(),
+        },
+        expected_minutes: data.expected_minutes(),
+        client_amounts: client_amounts(&data.amounts, &data.registry),
+        total_amounts: total_amounts(&data.amounts),
+        periods: data.periods,
+        timeline: data.timeline,
+        daily: data.daily,
+        heatmap: data.heatmap,
+        billing: data.billing,
+        budgets: data.budgets,
+        retainers: data.retainers,
+        balance: data.balance,
+        entries: data.entries,
+        warnings: Vector::new(),
+        comparison: None,
+        rounding: None,
+    })
+}
+
+/// Computes the report for the day entries within the date range.  Entries
+/// outside of the range are used only for budgets and the flex-time balance,
+/// which ignore the filter.
+/// Period tables are only computed for the monthly and yearly layouts, the
+/// timeline only for the timeline layout, the listings only for the daily
+/// layout and the heatmap only for the heatmap layout.
+pub fn compute_report(
+    dates: DateRange,
+    day_entries: &Vector<DayEntry>,
+    mode: ReportMode,
+    layout: ReportLayout,
+    filter: &Filter,
+    config: &Config,
+) -> Result<Report> {
+    let data = compute_report_data(dates, day_entries, mode, layout, filter, config)?;
+    build_report(data, mode, layout)
 }
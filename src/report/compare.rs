@@ -0,0 +1,170 @@
+use super::{Minutes, ReportLayout, ReportMode, compute_report_data};
+use crate::config::Config;
+use crate::filter::Filter;
+use crate::model::{Date, DateRange, DayEntry, Project};
+use anyhow::Result;
+use derive_getters::Getters;
+use im::{OrdSet, Vector};
+
+/// Which earlier dates a report is compared with.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ComparisonKind {
+    /// The period just before the report dates.
+    Previous,
+    /// The same dates one year earlier.
+    LastYear,
+}
+
+impl ComparisonKind {
+    /// Kind for a name accepted by the `--compare` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "previous" => Some(ComparisonKind::Previous),
+            "year" => Some(ComparisonKind::LastYear),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComparisonKind::Previous => "previous",
+            ComparisonKind::LastYear => "year",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ComparisonKind::Previous => "Previous Period",
+            ComparisonKind::LastYear => "Last Year",
+        }
+    }
+
+    /// Dates to compare with.  The period before a semi-monthly period or a
+    /// calendar month is the previous one of those, otherwise it is the same
+    /// number of days just before the report.
+    pub fn shift(&self, dates: &DateRange) -> Result<DateRange> {
+        let first = *dates.first();
+        let last = *dates.last();
+        let shifted = match self {
+            ComparisonKind::LastYear => DateRange::new(first.year_earlier()?, last.year_earlier()?),
+            ComparisonKind::Previous if *dates == first.semimonth_for_date() => {
+                first.prev()?.semimonth_for_date()
+            }
+            ComparisonKind::Previous
+                if *first.day() == 1
+                    && last.is_month_end()
+                    && first.year() == last.year()
+                    && first.month() == last.month() =>
+            {
+                let end = first.prev()?;
+                DateRange::new(Date::new(*end.year(), *end.month(), 1)?, end)
+            }
+            ComparisonKind::Previous => {
+                let days = dates.iter().count() as i32;
+                DateRange::new(first.minus_days(days)?, first.prev()?)
+            }
+        };
+        Ok(shifted)
+    }
+}
+
+/// Time in the report dates and in the dates compared with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Getters)]
+pub struct MinutesChange {
+    current: Minutes,
+    previous: Minutes,
+}
+
+impl MinutesChange {
+    /// Minutes worked less the minutes worked in the dates compared with.
+    pub fn change_minutes(&self) -> i32 {
+        self.current.minutes as i32 - self.previous.minutes as i32
+    }
+
+    /// Change as a whole percentage of the earlier minutes or `None` when no
+    /// time was worked in the earlier dates.
+    pub fn change_percent(&self) -> Option<i32> {
+        match self.previous.minutes {
+            0 => None,
+            previous => {
+                Some((self.change_minutes() as f64 * 100.0 / previous as f64).round() as i32)
+            }
+        }
+    }
+}
+
+/// Time for one project in both periods.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ProjectComparison {
+    project: Project,
+    name: Option<String>,
+    change: MinutesChange,
+}
+
+impl ProjectComparison {
+    /// Display name or the full project label.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.project.format())
+    }
+}
+
+/// Time for each project in the report dates side by side with the time in
+/// some earlier dates.  Projects worked in either period are included.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Comparison {
+    kind: ComparisonKind,
+    dates: DateRange,
+    previous_dates: DateRange,
+    projects: Vector<ProjectComparison>,
+    totals: MinutesChange,
+}
+
+/// Computes the report totals for the dates and the shifted dates using the
+/// same mode and filter and pairs them up by project.
+pub fn compute_comparison(
+    dates: DateRange,
+    kind: ComparisonKind,
+    day_entries: &Vector<DayEntry>,
+    mode: ReportMode,
+    filter: &Filter,
+    config: &Config,
+) -> Result<Comparison> {
+    let previous_dates = kind.shift(&dates)?;
+    let layout = ReportLayout::Weekly;
+    let current = compute_report_data(dates, day_entries, mode, layout, filter, config)?;
+    let previous = compute_report_data(previous_dates, day_entries, mode, layout, filter, config)?;
+    let projects: OrdSet<Project> = current.projects.clone().union(previous.projects.clone());
+    let projects = projects
+        .iter()
+        .map(|p| ProjectComparison {
+            project: p.clone(),
+            name: config.registry().project_name(p).map(|n| n.to_string()),
+            change: MinutesChange {
+                current: Minutes {
+                    minutes: current.totals.project_total(p),
+                    billable: current.totals.project_billable(p),
+                },
+                previous: Minutes {
+                    minutes: previous.totals.project_total(p),
+                    billable: previous.totals.project_billable(p),
+                },
+            },
+        })
+        .collect();
+    Ok(Comparison {
+        kind,
+        dates,
+        previous_dates,
+        projects,
+        totals: MinutesChange {
+            current: Minutes {
+                minutes: current.totals.week_total(),
+                billable: current.totals.week_billable(),
+            },
+            previous: Minutes {
+                minutes: previous.totals.week_total(),
+                billable: previous.totals.week_billable(),
+            },
+        },
+    })
+}
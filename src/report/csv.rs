@@ -0,0 +1,93 @@
+use super::{Minutes, Report, ReportRenderer};
+use crate::model::Project;
+use anyhow::Result;
+use im::Vector;
+
+#[cfg(test)]
+mod tests;
+
+/// Rows produced by a CSV report.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CsvLayout {
+    /// One row per date and project.
+    Daily,
+    /// One row per week and project.
+    Weekly,
+}
+
+/// Quotes a field if it contains characters that have meaning in CSV.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn csv_row(first: &str, project: &Project, minutes: Minutes) -> String {
+    [
+        first,
+        project.client(),
+        project.code(),
+        project.subcode(),
+        &minutes.minutes.to_string(),
+        &minutes.billable.to_string(),
+    ]
+    .iter()
+    .map(|f| csv_field(f))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Renders the report as CSV lines starting with a header line.  Weekly rows
+/// are labelled with the Monday of the week.
+pub struct CsvRenderer {
+    layout: CsvLayout,
+}
+
+impl CsvRenderer {
+    pub fn new(layout: CsvLayout) -> Self {
+        CsvRenderer { layout }
+    }
+}
+
+fn render_daily(report: &Report) -> Vector<String> {
+    let mut answer = Vector::new();
+    answer.push_back("date,client,code,subcode,minutes,billable_minutes".to_string());
+    for week in report.weeks.iter() {
+        for (index, day) in week.days.iter().enumerate() {
+            if !report.dates.contains(&day.date) {
+                continue;
+            }
+            for p in week.projects.iter() {
+                let minutes = p.days[index];
+                if minutes.minutes > 0 {
+                    answer.push_back(csv_row(&day.date.to_string(), &p.project, minutes));
+                }
+            }
+        }
+    }
+    answer
+}
+
+fn render_weekly(report: &Report) -> Vector<String> {
+    let mut answer = Vector::new();
+    answer.push_back("week,client,code,subcode,minutes,billable_minutes".to_string());
+    for week in report.weeks.iter() {
+        for p in week.projects.iter() {
+            if p.totals.minutes > 0 {
+                answer.push_back(csv_row(&week.monday.to_string(), &p.project, p.totals));
+            }
+        }
+    }
+    answer
+}
+
+impl ReportRenderer for CsvRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>> {
+        match self.layout {
+            CsvLayout::Daily => Ok(render_daily(report)),
+            CsvLayout::Weekly => Ok(render_weekly(report)),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use super::*;
+
+#[test]
+fn test_csv_field() {
+    assert_eq!("acme", csv_field("acme"));
+    assert_eq!("\"a,b\"", csv_field("a,b"));
+    assert_eq!("\"say \"\"hi\"\"\"", csv_field("say \"hi\""));
+}
@@ -0,0 +1,29 @@
+use super::{BillableMinutes, Minutes, day_totals};
+use crate::model::{Date, DayEntry, ProjectTimes};
+use derive_getters::Getters;
+use im::Vector;
+
+/// Every project line recorded on one date in the order it appears in the
+/// time log along with the totals for the date.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct DailyListing {
+    date: Date,
+    projects: Vector<ProjectTimes>,
+    totals: Minutes,
+}
+
+/// Computes one listing per date with time recorded from day entries already
+/// limited to the report dates and adjusted for the report mode.
+pub fn compute_daily(
+    day_entries: &Vector<DayEntry>,
+    billable: &BillableMinutes,
+) -> Vector<DailyListing> {
+    day_entries
+        .iter()
+        .map(|entry| DailyListing {
+            date: *entry.date(),
+            projects: entry.projects().clone(),
+            totals: day_totals(entry, billable),
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+use crate::model::{DAY_ABBREVS, DayEntry};
+use derive_getters::Getters;
+use im::Vector;
+
+const DEFAULT_FIRST_HOUR: u16 = 8;
+const DEFAULT_LAST_HOUR: u16 = 18;
+
+/// Characters drawn in text heatmaps from no time up to the busiest hour.
+const SHADES: [char; 5] = ['.', '░', '▒', '▓', '█'];
+
+/// Minutes worked in each hour of the day for one day of the week summed
+/// over every week of the report.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct HeatmapRow {
+    day: String,
+    hours: Vector<u32>,
+    total: u32,
+}
+
+/// Minutes worked by day of the week and hour of the day.  Every row covers
+/// the same hours so the columns line up.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Heatmap {
+    first_hour: u16,
+    last_hour: u16,
+    rows: Vector<HeatmapRow>,
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Heatmap {
+            first_hour: DEFAULT_FIRST_HOUR,
+            last_hour: DEFAULT_LAST_HOUR,
+            rows: Vector::new(),
+        }
+    }
+}
+
+impl Heatmap {
+    /// Hours at the start of each column.
+    pub fn hours(&self) -> impl Iterator<Item = u16> {
+        self.first_hour..self.last_hour
+    }
+
+    /// Most minutes worked in any one cell.
+    pub fn max_minutes(&self) -> u32 {
+        self.rows
+            .iter()
+            .flat_map(|r| r.hours.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Intensity of a cell from 0 for no time to 4 for the busiest cells.
+    pub fn level(&self, minutes: u32) -> usize {
+        match (minutes, self.max_minutes()) {
+            (0, _) | (_, 0) => 0,
+            (m, max) => 1 + ((m * 4 - 1) / max).min(3) as usize,
+        }
+    }
+
+    /// Character drawn in text heatmaps for a cell.
+    pub fn shade(&self, minutes: u32) -> char {
+        SHADES[self.level(minutes)]
+    }
+
+    /// Most minutes in a cell drawn at the level.
+    pub fn level_limit(&self, level: usize) -> u32 {
+        self.max_minutes() * level.min(4) as u32 / 4
+    }
+
+    /// Characters drawn for each level from no time up to the busiest cells.
+    pub fn shades() -> [char; 5] {
+        SHADES
+    }
+}
+
+/// Computes the heatmap for day entries already limited to the report dates.
+/// The hours run from 8:00 to 18:00 widened as needed to include every range.
+pub fn compute_heatmap(day_entries: &Vector<DayEntry>) -> Heatmap {
+    let mut minutes = [[0u32; 24]; 7];
+    let mut first_hour = DEFAULT_FIRST_HOUR;
+    let mut last_hour = DEFAULT_LAST_HOUR;
+    for entry in day_entries {
+        let day = (entry.date().day_num() % 7) as usize;
+        for range in entry.projects().iter().flat_map(|p| p.time_ranges().iter()) {
+            let from = range.from().minute_of_day();
+            let to = range.to().minute_of_day();
+            if from == to {
+                continue;
+            }
+            first_hour = first_hour.min(from / 60);
+            last_hour = last_hour.max(to.div_ceil(60));
+            for hour in from / 60..to.div_ceil(60).min(24) {
+                let start = from.max(hour * 60);
+                let end = to.min(hour * 60 + 60);
+                minutes[day][hour as usize] += (end - start) as u32;
+            }
+        }
+    }
+    Heatmap {
+        first_hour,
+        last_hour,
+        rows: DAY_ABBREVS
+            .iter()
+            .zip(minutes.iter())
+            .map(|(day, hours)| HeatmapRow {
+                day: day.clone(),
+                hours: hours[first_hour as usize..last_hour as usize]
+                    .iter()
+                    .copied()
+                    .collect(),
+                total: hours.iter().sum(),
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,136 @@
+use super::table::{Row, RowKind, Table, report_tables};
+use super::timeline::symbol_index;
+use super::{Report, ReportRenderer};
+use anyhow::Result;
+use im::Vector;
+
+/// Renders the report as a standalone HTML page with an embedded style sheet.
+/// Weekend columns are shaded, totals are bold and the delta is green when
+/// ahead of the expected hours and red when behind.  Timeline bars are drawn
+/// as blocks coloured by project.
+pub struct HtmlRenderer;
+
+const STYLE: &str = "<style>
+body { font-family: sans-serif; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; text-align: left; }
+th { background: #eee; }
+.num { text-align: right; font-variant-numeric: tabular-nums; }
+.weekend { background: #f4f4f4; }
+th.weekend { background: #e2e2e2; }
+tr.total td { font-weight: bold; }
+tr.ahead td.num { color: #1a7f37; font-weight: bold; }
+tr.behind td.num { color: #c62828; font-weight: bold; }
+td.bar { white-space: nowrap; line-height: 0; }
+.slot { display: inline-block; width: 0.5em; height: 1.2em; background: #f0f0f0; }
+</style>";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Colours of the project symbols in timeline bars in symbol order.
+const PALETTE: [&str; 12] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf", "#393b79", "#637939",
+];
+
+/// Draws each symbol of a timeline bar as a block in the project's colour.
+fn render_bar(bar: &str) -> String {
+    bar.chars()
+        .map(|c| match (c, symbol_index(c)) {
+            ('.', _) => r#"<span class="slot"></span>"#.to_string(),
+            (_, Some(index)) => format!(
+                r#"<span class="slot" style="background: {}" title="{c}"></span>"#,
+                PALETTE[index % PALETTE.len()]
+            ),
+            (_, None) => {
+                format!(r#"<span class="slot" style="background: #333" title="{c}"></span>"#)
+            }
+        })
+        .collect()
+}
+
+fn render_cells(tag: &str, table: &Table, cells: &[String]) -> String {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let mut classes = Vec::new();
+            if i > 0 && !table.text_columns.contains(&i) {
+                classes.push("num");
+            }
+            if table.weekend_columns.contains(&i) {
+                classes.push("weekend");
+            }
+            let bar = tag == "td" && table.bar_columns.contains(&i);
+            if bar {
+                classes.push("bar");
+            }
+            let class = if classes.is_empty() {
+                String::new()
+            } else {
+                format!(r#" class="{}""#, classes.join(" "))
+            };
+            let content = if bar { render_bar(c) } else { escape(c) };
+            format!("<{tag}{class}>{content}</{tag}>")
+        })
+        .collect()
+}
+
+fn row_class(row: &Row) -> &'static str {
+    match row.kind {
+        RowKind::Item => "",
+        RowKind::Total => r#" class="total""#,
+        RowKind::Delta(minutes) if minutes < 0 => r#" class="total behind""#,
+        RowKind::Delta(_) => r#" class="total ahead""#,
+    }
+}
+
+fn render_table(table: &Table) -> Vector<String> {
+    let mut answer = Vector::new();
+    answer.push_back(format!("<h2>{}</h2>", escape(&table.title)));
+    answer.push_back("<table>".to_string());
+    answer.push_back(format!(
+        "<tr>{}</tr>",
+        render_cells("th", table, &table.columns)
+    ));
+    for row in table.rows.iter() {
+        answer.push_back(format!(
+            "<tr{}>{}</tr>",
+            row_class(row),
+            render_cells("td", table, &row.cells)
+        ));
+    }
+    answer.push_back("</table>".to_string());
+    answer
+}
+
+impl ReportRenderer for HtmlRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>> {
+        let title = format!(
+            "Time Report {} - {}",
+            report.dates.first(),
+            report.dates.last()
+        );
+        let mut answer = Vector::new();
+        answer.push_back("<!DOCTYPE html>".to_string());
+        answer.push_back("<html>".to_string());
+        answer.push_back("<head>".to_string());
+        answer.push_back(r#"<meta charset="utf-8">"#.to_string());
+        answer.push_back(format!("<title>{title}</title>"));
+        answer.extend(STYLE.lines().map(|l| l.to_string()));
+        answer.push_back("</head>".to_string());
+        answer.push_back("<body>".to_string());
+        answer.push_back(format!("<h1>{title}</h1>"));
+        for table in report_tables(report) {
+            answer.append(render_table(&table));
+        }
+        answer.push_back("</body>".to_string());
+        answer.push_back("</html>".to_string());
+        Ok(answer)
+    }
+}
@@ -0,0 +1,343 @@
+use super::compare::{Comparison, MinutesChange};
+use super::heatmap::Heatmap;
+use super::rounding::{ProjectRounding, RoundingReport, rounding_minutes};
+use super::{
+    ClientReport, Minutes, PeriodReport, ProjectReport, Report, ReportLayout, ReportMode,
+    ReportRenderer, WeekReport,
+};
+use crate::balance::BalanceStatus;
+use crate::model::{DayEntry, Project, ProjectTimes, TimeRange};
+use crate::rates::Amounts;
+use crate::retainer::RetainerLedger;
+use anyhow::Result;
+use im::Vector;
+use serde_json::{Map, Value, json};
+
+/// Renders the report as a pretty printed JSON document.  The schema is
+/// described in the README.
+pub struct JsonRenderer;
+
+fn amounts_json(amounts: Option<&Amounts>) -> Value {
+    let mut map = Map::new();
+    for (currency, cents) in amounts.iter().flat_map(|a| a.iter()) {
+        map.insert(currency.clone(), json!(cents));
+    }
+    Value::Object(map)
+}
+
+fn project_json(project: &Project, minutes: Minutes) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("client".into(), json!(project.client()));
+    map.insert("code".into(), json!(project.code()));
+    map.insert("subcode".into(), json!(project.subcode()));
+    map.insert("minutes".into(), json!(minutes.minutes));
+    map.insert("billable_minutes".into(), json!(minutes.billable));
+    map
+}
+
+fn project_report_json(project: &ProjectReport) -> Value {
+    let mut map = project_json(&project.project, project.totals);
+    if let Some(name) = &project.name {
+        map.insert("name".into(), json!(name));
+    }
+    map.insert("amounts".into(), amounts_json(project.amounts.as_ref()));
+    Value::Object(map)
+}
+
+fn client_json(client: &ClientReport) -> Value {
+    let mut map = Map::new();
+    map.insert("client".into(), json!(client.client()));
+    if let Some(name) = client.name() {
+        map.insert("name".into(), json!(name));
+    }
+    map.insert("minutes".into(), json!(client.totals().minutes));
+    map.insert("billable_minutes".into(), json!(client.totals().billable));
+    map.insert("amounts".into(), amounts_json(client.amounts().as_ref()));
+    Value::Object(map)
+}
+
+fn week_json(report: &Report, week: &WeekReport) -> Value {
+    let days: Vec<Value> = week
+        .days
+        .iter()
+        .filter(|d| report.dates.contains(&d.date))
+        .map(|d| {
+            json!({
+                "date": d.date.to_string(),
+                "minutes": d.totals.minutes,
+                "billable_minutes": d.totals.billable,
+            })
+        })
+        .collect();
+    let projects: Vec<Value> = week
+        .projects
+        .iter()
+        .filter(|p| p.totals.minutes > 0 || p.target.is_some())
+        .map(|p| {
+            let mut map = project_json(&p.project, p.totals);
+            if let Some(status) = p.target() {
+                map.insert("target_minutes".into(), json!(status.target_minutes()));
+                map.insert("target_delta_minutes".into(), json!(status.delta_minutes()));
+                map.insert("target_status".into(), json!(status.marker()));
+            }
+            Value::Object(map)
+        })
+        .collect();
+    json!({
+        "monday": week.monday.to_string(),
+        "days": days,
+        "projects": projects,
+        "minutes": week.totals.minutes,
+        "billable_minutes": week.totals.billable,
+    })
+}
+
+fn period_json(period: &PeriodReport) -> Value {
+    let columns: Vec<Value> = period
+        .columns()
+        .iter()
+        .map(|c| {
+            json!({
+                "label": c.label(),
+                "first": c.dates().first().to_string(),
+                "last": c.dates().last().to_string(),
+                "minutes": c.totals().minutes,
+                "billable_minutes": c.totals().billable,
+                "expected_minutes": c.expected_minutes(),
+            })
+        })
+        .collect();
+    let projects: Vec<Value> = period
+        .projects()
+        .iter()
+        .map(|p| {
+            let mut map = project_json(p.project(), *p.totals());
+            let minutes: Vec<Value> = p
+                .columns()
+                .iter()
+                .map(|m| json!({"minutes": m.minutes, "billable_minutes": m.billable}))
+                .collect();
+            map.insert("columns".into(), json!(minutes));
+            Value::Object(map)
+        })
+        .collect();
+    json!({
+        "title": period.title(),
+        "columns": columns,
+        "projects": projects,
+        "minutes": period.totals().minutes,
+        "billable_minutes": period.totals().billable,
+        "expected_minutes": period.expected_minutes(),
+        "delta_minutes": period.delta_minutes(),
+    })
+}
+
+fn balance_json(status: &BalanceStatus) -> Value {
+    json!({
+        "from": status.balance().from().to_string(),
+        "through": status.through().to_string(),
+        "billable_minutes": status.billable_minutes(),
+        "expected_minutes": status.expected_minutes(),
+        "delta_minutes": status.delta_minutes(),
+        "overtime_minutes": status.overtime_minutes(),
+    })
+}
+
+fn comparison_json(comparison: &Comparison) -> Value {
+    let change_json = |mut map: Map<String, Value>, change: &MinutesChange| {
+        map.insert("minutes".into(), json!(change.current().minutes));
+        map.insert("billable_minutes".into(), json!(change.current().billable));
+        map.insert("previous_minutes".into(), json!(change.previous().minutes));
+        map.insert(
+            "previous_billable_minutes".into(),
+            json!(change.previous().billable),
+        );
+        map.insert("change_minutes".into(), json!(change.change_minutes()));
+        map.insert("change_percent".into(), json!(change.change_percent()));
+        Value::Object(map)
+    };
+    let projects: Vec<Value> = comparison
+        .projects()
+        .iter()
+        .map(|p| {
+            let mut map = Map::new();
+            map.insert("client".into(), json!(p.project().client()));
+            map.insert("code".into(), json!(p.project().code()));
+            map.insert("subcode".into(), json!(p.project().subcode()));
+            if let Some(name) = p.name() {
+                map.insert("name".into(), json!(name));
+            }
+            change_json(map, p.change())
+        })
+        .collect();
+    let mut map = Map::new();
+    map.insert("kind".into(), json!(comparison.kind().name()));
+    map.insert(
+        "first".into(),
+        json!(comparison.previous_dates().first().to_string()),
+    );
+    map.insert(
+        "last".into(),
+        json!(comparison.previous_dates().last().to_string()),
+    );
+    map.insert("projects".into(), json!(projects));
+    change_json(map, comparison.totals())
+}
+
+fn rounding_json(rounding: &RoundingReport) -> Value {
+    let minutes_json = |mut map: Map<String, Value>, minutes: &Minutes| {
+        map.insert("minutes".into(), json!(minutes.minutes));
+        map.insert("billable_minutes".into(), json!(minutes.billable));
+        map.insert("rounding_minutes".into(), json!(rounding_minutes(minutes)));
+        Value::Object(map)
+    };
+    let projects = |projects: &Vector<ProjectRounding>| {
+        projects
+            .iter()
+            .map(|p| {
+                let mut map = Map::new();
+                map.insert("client".into(), json!(p.project().client()));
+                map.insert("code".into(), json!(p.project().code()));
+                map.insert("subcode".into(), json!(p.project().subcode()));
+                if let Some(name) = p.name() {
+                    map.insert("name".into(), json!(name));
+                }
+                minutes_json(map, p.minutes())
+            })
+            .collect::<Vec<_>>()
+    };
+    let periods: Vec<Value> = rounding
+        .periods()
+        .iter()
+        .map(|period| {
+            let mut map = Map::new();
+            map.insert("label".into(), json!(period.label()));
+            map.insert("first".into(), json!(period.dates().first().to_string()));
+            map.insert("last".into(), json!(period.dates().last().to_string()));
+            map.insert("projects".into(), json!(projects(period.projects())));
+            minutes_json(map, period.totals())
+        })
+        .collect();
+    let mut map = Map::new();
+    map.insert("period".into(), json!(rounding.period().name()));
+    map.insert("periods".into(), json!(periods));
+    map.insert("projects".into(), json!(projects(rounding.projects())));
+    minutes_json(map, rounding.totals())
+}
+
+fn heatmap_json(heatmap: &Heatmap) -> Value {
+    json!({
+        "first_hour": heatmap.first_hour(),
+        "last_hour": heatmap.last_hour(),
+        "days": heatmap
+            .rows()
+            .iter()
+            .map(|r| json!({
+                "day": r.day(),
+                "hours": r.hours().iter().collect::<Vec<_>>(),
+                "minutes": r.total(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn retainer_json(ledger: &RetainerLedger) -> Value {
+    json!({
+        "client": ledger.retainer().client(),
+        "monthly_minutes": ledger.retainer().monthly_minutes(),
+        "rollover_cap_minutes": ledger.retainer().rollover_cap_minutes(),
+        "months": ledger
+            .months()
+            .iter()
+            .map(|m| json!({
+                "first": m.dates().first().to_string(),
+                "last": m.dates().last().to_string(),
+                "allotted_minutes": m.allotted_minutes(),
+                "rolled_over_minutes": m.rolled_over_minutes(),
+                "used_minutes": m.used_minutes(),
+                "remaining_minutes": m.remaining_minutes(),
+                "overage_minutes": m.overage_minutes(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn time_range_json(range: &TimeRange) -> Value {
+    json!({
+        "from": range.from().to_string(),
+        "to": range.to().to_string(),
+        "minutes": range.duration(),
+    })
+}
+
+fn project_times_json(times: &ProjectTimes) -> Value {
+    json!({
+        "client": times.project().client(),
+        "code": times.project().code(),
+        "subcode": times.project().subcode(),
+        "note": times.note(),
+        "ranges": times.time_ranges().iter().map(time_range_json).collect::<Vec<_>>(),
+    })
+}
+
+fn entry_json(entry: &DayEntry) -> Value {
+    json!({
+        "date": entry.date().to_string(),
+        "line_number": entry.line_number(),
+        "projects": entry.projects().iter().map(project_times_json).collect::<Vec<_>>(),
+    })
+}
+
+impl ReportRenderer for JsonRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>> {
+        let mut clients = Map::new();
+        for (client, amounts) in report.client_amounts.iter() {
+            clients.insert(client.clone(), amounts_json(Some(amounts)));
+        }
+        let document = json!({
+            "first": report.dates.first().to_string(),
+            "last": report.dates.last().to_string(),
+            "mode": match report.mode {
+                ReportMode::Detail => "detail",
+                ReportMode::Summary => "summary",
+                ReportMode::Client => "client",
+            },
+            "layout": report.layout.name(),
+            "weeks": report.weeks.iter().map(|w| week_json(report, w)).collect::<Vec<_>>(),
+            "periods": report.periods.iter().map(period_json).collect::<Vec<_>>(),
+            "projects": report.projects.iter().map(project_report_json).collect::<Vec<_>>(),
+            "clients": report.clients.iter().map(client_json).collect::<Vec<_>>(),
+            "minutes": report.totals.minutes,
+            "billable_minutes": report.totals.billable,
+            "expected_minutes": report.expected_minutes,
+            "delta_minutes": report.delta_minutes(),
+            "amounts": amounts_json(Some(&report.total_amounts)),
+            "client_amounts": clients,
+            "warnings": report.warnings.iter().collect::<Vec<_>>(),
+            "entries": report.entries.iter().map(entry_json).collect::<Vec<_>>(),
+        });
+        let mut document = document;
+        if report.layout == ReportLayout::Heatmap {
+            document["heatmap"] = heatmap_json(&report.heatmap);
+        }
+        if let Some(balance) = &report.balance {
+            document["balance"] = balance_json(balance);
+        }
+        if let Some(comparison) = &report.comparison {
+            document["comparison"] = comparison_json(comparison);
+        }
+        if let Some(rounding) = &report.rounding {
+            document["rounding"] = rounding_json(rounding);
+        }
+        if !report.retainers.is_empty() {
+            document["retainers"] = json!(
+                report
+                    .retainers
+                    .iter()
+                    .map(retainer_json)
+                    .collect::<Vec<_>>()
+            );
+        }
+        Ok(Vector::unit(serde_json::to_string_pretty(&document)?))
+    }
+}
@@ -0,0 +1,79 @@
+use super::table::{RowKind, Table, report_tables};
+use super::{Report, ReportRenderer};
+use anyhow::Result;
+use im::Vector;
+
+/// Renders the report as Markdown tables with the totals in bold and timeline
+/// bars as code.
+pub struct MarkdownRenderer;
+
+/// Text with the characters that Markdown would treat as markup, HTML or table
+/// cell separators escaped.
+pub fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|")
+        .replace('_', "\\_")
+        .replace('*', "\\*")
+        .replace('`', "\\`")
+}
+
+fn render_row(cells: &[String], bar_columns: &[usize], bold: bool) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if bar_columns.contains(&i) && !c.is_empty() {
+                format!("`{c}`")
+            } else if bold && !c.is_empty() {
+                format!("**{}**", escape_markdown(c))
+            } else {
+                escape_markdown(c)
+            }
+        })
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+fn render_table(table: &Table) -> Vector<String> {
+    let mut answer = Vector::new();
+    answer.push_back(format!("## {}", table.title));
+    answer.push_back("".to_string());
+    answer.push_back(render_row(&table.columns, &[], false));
+    let alignments: Vec<&str> = (0..table.columns.len())
+        .map(|i| {
+            if i == 0 || table.text_columns.contains(&i) {
+                ":---"
+            } else {
+                "---:"
+            }
+        })
+        .collect();
+    answer.push_back(format!("| {} |", alignments.join(" | ")));
+    for row in table.rows.iter() {
+        answer.push_back(render_row(
+            &row.cells,
+            &table.bar_columns,
+            row.kind != RowKind::Item,
+        ));
+    }
+    answer
+}
+
+impl ReportRenderer for MarkdownRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>> {
+        let mut answer = Vector::new();
+        answer.push_back(format!(
+            "# Time Report {} - {}",
+            report.dates.first(),
+            report.dates.last()
+        ));
+        for table in report_tables(report) {
+            answer.push_back("".to_string());
+            answer.append(render_table(&table));
+        }
+        Ok(answer)
+    }
+}
@@ -0,0 +1,210 @@
+use super::{BillableMinutes, Minutes, ReportLayout, billable_minutes, recorded_billable};
+use crate::model::{Date, DateRange, DayEntry, Project};
+use derive_getters::Getters;
+use im::{OrdMap, OrdSet, Vector};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub(super) fn month_name(date: &Date) -> &'static str {
+    MONTH_NAMES[*date.month() as usize - 1]
+}
+
+/// Totals for one column of a period table: a week within a month or a month
+/// within a year, limited to the report dates.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct PeriodColumn {
+    label: String,
+    dates: DateRange,
+    totals: Minutes,
+    expected_minutes: u32,
+}
+
+impl PeriodColumn {
+    /// Billable minutes less expected minutes.
+    pub fn delta_minutes(&self) -> i32 {
+        self.totals.billable as i32 - self.expected_minutes as i32
+    }
+}
+
+/// Time for one project in each column of a period table.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ProjectPeriods {
+    project: Project,
+    columns: Vector<Minutes>,
+    totals: Minutes,
+}
+
+/// Table of projects by week for one month or by month for one year.  Only
+/// projects with time recorded in the period are included.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct PeriodReport {
+    title: String,
+    columns: Vector<PeriodColumn>,
+    projects: Vector<ProjectPeriods>,
+    totals: Minutes,
+    expected_minutes: u32,
+}
+
+impl PeriodReport {
+    /// Billable minutes less expected minutes.
+    pub fn delta_minutes(&self) -> i32 {
+        self.totals.billable as i32 - self.expected_minutes as i32
+    }
+}
+
+/// Title of the table holding a date and a key ordering its column within the
+/// report.  Columns with the same title are placed in the same table.
+fn period_keys(layout: ReportLayout, date: &Date) -> Option<(String, (u16, u8, u32))> {
+    match layout {
+        ReportLayout::Weekly
+        | ReportLayout::Timeline
+        | ReportLayout::Daily
+        | ReportLayout::Heatmap => None,
+        ReportLayout::Monthly => Some((
+            format!("{} {}", month_name(date), date.year()),
+            (*date.year(), *date.month(), date.week_num()),
+        )),
+        ReportLayout::Yearly => Some((date.year().to_string(), (*date.year(), *date.month(), 0))),
+    }
+}
+
+fn column_label(layout: ReportLayout, dates: &DateRange) -> String {
+    match layout {
+        ReportLayout::Yearly => month_name(dates.first())[..3].to_uppercase(),
+        _ => format!("{:02}/{:02}", dates.first().month(), dates.first().day()),
+    }
+}
+
+fn sum(minutes: impl Iterator<Item = Minutes>) -> Minutes {
+    minutes.fold(Minutes::default(), |a, m| Minutes {
+        minutes: a.minutes + m.minutes,
+        billable: a.billable + m.billable,
+    })
+}
+
+/// Minutes for each project and date.  Billable minutes are rounded per day
+/// as they are for billing.
+fn project_date_minutes(
+    day_entries: &Vector<DayEntry>,
+    billable: &BillableMinutes,
+) -> OrdMap<(Project, Date), Minutes> {
+    let mut totals = OrdMap::<(Project, Date), u32>::new();
+    for entry in day_entries {
+        for p in entry.projects() {
+            *totals
+                .entry((p.project().clone(), *entry.date()))
+                .or_default() += p.total_minutes();
+        }
+    }
+    totals
+        .into_iter()
+        .map(|((project, date), m)| {
+            let minutes = Minutes {
+                minutes: m,
+                billable: billable_minutes(recorded_billable(billable, &project, &date)),
+            };
+            ((project, date), minutes)
+        })
+        .collect()
+}
+
+/// Computes one table per month or year in the date range.  Day entries must
+/// already be limited to the range and adjusted for the report mode.  Expected
+/// minutes are eight hours for each weekday with time recorded.
+pub fn compute_periods(
+    layout: ReportLayout,
+    dates: &DateRange,
+    day_entries: &Vector<DayEntry>,
+    billable: &BillableMinutes,
+) -> Vector<PeriodReport> {
+    let mut groups = OrdMap::<(u16, u8, u32), (String, Vector<Date>)>::new();
+    for date in dates.iter() {
+        if let Some((title, column)) = period_keys(layout, &date) {
+            groups
+                .entry(column)
+                .or_insert_with(|| (title, Vector::new()))
+                .1
+                .push_back(date);
+        }
+    }
+    let minutes = project_date_minutes(day_entries, billable);
+    let weekdays: OrdMap<Date, u32> = day_entries.iter().filter(|e| e.date().is_weekday()).fold(
+        OrdMap::new(),
+        |mut counts, e| {
+            *counts.entry(*e.date()).or_default() += 1;
+            counts
+        },
+    );
+    let projects: OrdSet<Project> = minutes.keys().map(|(p, _)| p.clone()).collect();
+
+    let mut answer = Vector::<PeriodReport>::new();
+    for (title, column_dates) in groups.values() {
+        let column_range = DateRange::new(
+            *column_dates.front().unwrap(),
+            *column_dates.back().unwrap(),
+        );
+        let project_minutes = |p: &Project| {
+            sum(column_dates
+                .iter()
+                .filter_map(|d| minutes.get(&(p.clone(), *d)).copied()))
+        };
+        if answer.back().is_none_or(|r| r.title != *title) {
+            answer.push_back(PeriodReport {
+                title: title.clone(),
+                columns: Vector::new(),
+                projects: projects
+                    .iter()
+                    .map(|p| ProjectPeriods {
+                        project: p.clone(),
+                        columns: Vector::new(),
+                        totals: Minutes::default(),
+                    })
+                    .collect(),
+                totals: Minutes::default(),
+                expected_minutes: 0,
+            });
+        }
+        let report = answer.back_mut().unwrap();
+        let mut column_totals = Vector::new();
+        for row in report.projects.iter_mut() {
+            let m = project_minutes(&row.project);
+            row.columns.push_back(m);
+            column_totals.push_back(m);
+        }
+        let expected_minutes = 480
+            * column_dates
+                .iter()
+                .map(|d| weekdays.get(d).copied().unwrap_or(0))
+                .sum::<u32>();
+        report.columns.push_back(PeriodColumn {
+            label: column_label(layout, &column_range),
+            dates: column_range,
+            totals: sum(column_totals.into_iter()),
+            expected_minutes,
+        });
+    }
+    for report in answer.iter_mut() {
+        report
+            .projects
+            .retain(|p| p.columns.iter().any(|m| m.minutes > 0));
+        for row in report.projects.iter_mut() {
+            row.totals = sum(row.columns.iter().copied());
+        }
+        report.totals = sum(report.columns.iter().map(|c| c.totals));
+        report.expected_minutes = report.columns.iter().map(|c| c.expected_minutes).sum();
+    }
+    answer
+}
@@ -0,0 +1,181 @@
+use super::period::month_name;
+use super::{
+    Minutes, ReportMode, adjust_day_entry_for_mode, billable_minutes, day_entries_in_range,
+};
+use crate::config::Config;
+use crate::filter::Filter;
+use crate::model::{Date, DateRange, DayEntry, Project};
+use anyhow::Result;
+use derive_getters::Getters;
+use im::{OrdMap, Vector};
+
+/// Length of the periods totalled in the rounding report.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RoundingPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl RoundingPeriod {
+    /// Period for a name accepted by the `--rounding` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "daily" => Some(RoundingPeriod::Daily),
+            "weekly" => Some(RoundingPeriod::Weekly),
+            "monthly" => Some(RoundingPeriod::Monthly),
+            "yearly" => Some(RoundingPeriod::Yearly),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoundingPeriod::Daily => "daily",
+            RoundingPeriod::Weekly => "weekly",
+            RoundingPeriod::Monthly => "monthly",
+            RoundingPeriod::Yearly => "yearly",
+        }
+    }
+
+    /// Label of the period holding a date along with a key ordering it.
+    fn label(&self, date: &Date) -> Result<(Date, String)> {
+        let answer = match self {
+            RoundingPeriod::Daily => (*date, format!("{} {}", date.day_abbrev(), date)),
+            RoundingPeriod::Weekly => {
+                let monday = date.this_monday()?;
+                (monday, format!("Week of {monday}"))
+            }
+            RoundingPeriod::Monthly => (
+                Date::new(*date.year(), *date.month(), 1)?,
+                format!("{} {}", month_name(date), date.year()),
+            ),
+            RoundingPeriod::Yearly => (Date::new(*date.year(), 1, 1)?, date.year().to_string()),
+        };
+        Ok(answer)
+    }
+}
+
+/// Minutes recorded and billed for one project.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ProjectRounding {
+    project: Project,
+    name: Option<String>,
+    minutes: Minutes,
+}
+
+impl ProjectRounding {
+    /// Display name or the full project label.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.project.format())
+    }
+}
+
+/// Minutes recorded and billed for each project in one period.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct RoundingPeriodReport {
+    label: String,
+    dates: DateRange,
+    projects: Vector<ProjectRounding>,
+    totals: Minutes,
+}
+
+/// Time lost or gained by rounding billable time for each project in each
+/// period of the report dates.  Projects that are not billable are left out.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct RoundingReport {
+    period: RoundingPeriod,
+    periods: Vector<RoundingPeriodReport>,
+    projects: Vector<ProjectRounding>,
+    totals: Minutes,
+}
+
+/// Billable minutes less recorded minutes, negative when time is lost.
+pub fn rounding_minutes(minutes: &Minutes) -> i32 {
+    minutes.billable as i32 - minutes.minutes as i32
+}
+
+fn add(a: Minutes, b: Minutes) -> Minutes {
+    Minutes {
+        minutes: a.minutes + b.minutes,
+        billable: a.billable + b.billable,
+    }
+}
+
+fn sum(minutes: &OrdMap<Project, Minutes>) -> Minutes {
+    minutes.values().fold(Minutes::default(), |a, m| add(a, *m))
+}
+
+fn project_roundings(
+    minutes: &OrdMap<Project, Minutes>,
+    config: &Config,
+) -> Vector<ProjectRounding> {
+    minutes
+        .iter()
+        .map(|(project, m)| ProjectRounding {
+            project: project.clone(),
+            name: config
+                .registry()
+                .project_name(project)
+                .map(|n| n.to_string()),
+            minutes: *m,
+        })
+        .collect()
+}
+
+/// Computes the rounding report for the day entries within the date range
+/// using the same mode and filter as the report.  Billable minutes are
+/// rounded per project and day as they are for billing.
+pub fn compute_rounding(
+    dates: DateRange,
+    period: RoundingPeriod,
+    day_entries: &Vector<DayEntry>,
+    mode: ReportMode,
+    filter: &Filter,
+    config: &Config,
+) -> Result<RoundingReport> {
+    let registry = config.registry();
+    let entries = filter.apply(&day_entries_in_range(&dates, day_entries), registry);
+    let mut days = OrdMap::<(Date, Project), u32>::new();
+    for entry in entries.iter().map(|e| adjust_day_entry_for_mode(e, mode)) {
+        for p in entry.projects() {
+            if registry.is_billable(p.project()) {
+                *days
+                    .entry((*entry.date(), p.project().clone()))
+                    .or_default() += p.total_minutes();
+            }
+        }
+    }
+    let mut periods = OrdMap::<Date, (String, DateRange, OrdMap<Project, Minutes>)>::new();
+    let mut all = OrdMap::<Project, Minutes>::new();
+    for ((date, project), m) in days {
+        let minutes = Minutes {
+            minutes: m,
+            billable: billable_minutes(m),
+        };
+        let (key, label) = period.label(&date)?;
+        let (_, range, projects) = periods
+            .entry(key)
+            .or_insert_with(|| (label, DateRange::new(date, date), OrdMap::new()));
+        *range = DateRange::new(*range.first(), (*range.last()).max(date));
+        let total = projects.entry(project.clone()).or_default();
+        *total = add(*total, minutes);
+        let total = all.entry(project).or_default();
+        *total = add(*total, minutes);
+    }
+    Ok(RoundingReport {
+        period,
+        periods: periods
+            .into_iter()
+            .map(|(_, (label, dates, projects))| RoundingPeriodReport {
+                label,
+                dates,
+                totals: sum(&projects),
+                projects: project_roundings(&projects, config),
+            })
+            .collect(),
+        totals: sum(&all),
+        projects: project_roundings(&all, config),
+    })
+}
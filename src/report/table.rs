@@ -0,0 +1,538 @@
+use super::compare::{Comparison, MinutesChange};
+use super::heatmap::Heatmap;
+use super::period::month_name;
+use super::rounding::{RoundingReport, rounding_minutes};
+use super::timeline::{project_symbol, render_bar};
+use super::{Minutes, PeriodReport, Report, ReportLayout, ReportMode, render_delta, render_time};
+use crate::balance::BalanceStatus;
+use crate::rates::Amounts;
+
+/// How a row should be emphasized by renderers that support styling.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RowKind {
+    Item,
+    Total,
+    /// Difference from the expected time, positive when ahead.
+    Delta(i32),
+}
+
+pub struct Row {
+    pub kind: RowKind,
+    pub cells: Vec<String>,
+}
+
+/// Table with a left aligned label column followed by right aligned value columns.
+/// Every renderer other than CSV and JSON lays out its columns from these tables.
+pub struct Table {
+    pub title: String,
+    pub columns: Vec<String>,
+    /// Indexes of the columns holding Saturday and Sunday.
+    pub weekend_columns: Vec<usize>,
+    /// Indexes of the columns holding timeline bars made of project symbols.
+    pub bar_columns: Vec<usize>,
+    /// Indexes of the value columns holding text that should be left aligned.
+    pub text_columns: Vec<usize>,
+    pub rows: Vec<Row>,
+}
+
+impl Table {
+    fn new(title: &str, columns: &[&str]) -> Self {
+        Table {
+            title: title.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            weekend_columns: Vec::new(),
+            bar_columns: Vec::new(),
+            text_columns: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, kind: RowKind, cells: Vec<String>) {
+        self.rows.push(Row { kind, cells });
+    }
+}
+
+fn time(minutes: u32) -> String {
+    render_time(minutes, 1).trim().to_string()
+}
+
+/// Heading of the label column of tables listing the report's projects.
+fn row_heading(report: &Report) -> &'static str {
+    match report.mode {
+        ReportMode::Client => "Client",
+        _ => "Project",
+    }
+}
+
+fn amount(amounts: Option<&Amounts>) -> String {
+    amounts
+        .filter(|a| !a.is_empty())
+        .map_or("-".to_string(), |a| a.to_string())
+}
+
+fn week_tables(report: &Report) -> Vec<Table> {
+    let mut tables = Vec::new();
+    let targets = report.has_targets();
+    for week in report.weeks.iter() {
+        let mut columns = vec![row_heading(report).to_string()];
+        columns.extend(week.days.iter().map(|d| {
+            format!(
+                "{} {:02}/{:02}",
+                d.date.day_abbrev(),
+                d.date.month(),
+                d.date.day()
+            )
+        }));
+        columns.extend(["Totals".to_string(), "Report".to_string()]);
+        if targets {
+            columns.extend([
+                "Target".to_string(),
+                "Delta".to_string(),
+                "Status".to_string(),
+            ]);
+        }
+        let text_columns = match targets {
+            true => vec![columns.len() - 1],
+            false => Vec::new(),
+        };
+        let weekend_columns = week
+            .days
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.date.is_weekend())
+            .map(|(i, _)| i + 1)
+            .collect();
+        let mut table = Table {
+            title: format!("Week of {}", week.monday),
+            columns,
+            weekend_columns,
+            bar_columns: Vec::new(),
+            text_columns,
+            rows: Vec::new(),
+        };
+        let padding = |row: &mut Vec<String>| {
+            if targets {
+                row.extend([String::new(), String::new(), String::new()]);
+            }
+        };
+        let names = report.projects.iter().map(|p| p.label());
+        for (p, name) in week.projects.iter().zip(names) {
+            let mut row = vec![name];
+            row.extend(p.days.iter().map(|m| time(m.minutes)));
+            row.extend([time(p.totals.minutes), time(p.totals.billable)]);
+            match p.target() {
+                Some(status) => row.extend([
+                    time(*status.target_minutes()),
+                    delta(status.delta_minutes()),
+                    status.marker().to_string(),
+                ]),
+                None => padding(&mut row),
+            }
+            table.push(RowKind::Item, row);
+        }
+        let mut totals = vec!["Totals".to_string()];
+        totals.extend(week.days.iter().map(|d| time(d.totals.minutes)));
+        totals.extend([time(week.totals.minutes), String::new()]);
+        padding(&mut totals);
+        table.push(RowKind::Total, totals);
+        let mut billable = vec!["Report".to_string()];
+        billable.extend(week.days.iter().map(|d| time(d.totals.billable)));
+        billable.extend([String::new(), time(week.totals.billable)]);
+        padding(&mut billable);
+        table.push(RowKind::Total, billable);
+        tables.push(table);
+    }
+    tables
+}
+
+fn delta(minutes: i32) -> String {
+    render_delta(minutes, 1).trim().to_string()
+}
+
+fn period_table(report: &Report, period: &PeriodReport) -> Table {
+    let mut columns = vec![row_heading(report).to_string()];
+    columns.extend(period.columns().iter().map(|c| c.label().clone()));
+    columns.extend(["Totals".to_string(), "Report".to_string()]);
+    let mut table = Table {
+        title: period.title().clone(),
+        columns,
+        weekend_columns: Vec::new(),
+        bar_columns: Vec::new(),
+        text_columns: Vec::new(),
+        rows: Vec::new(),
+    };
+    for p in period.projects().iter() {
+        let mut row = vec![report.project_label(p.project())];
+        row.extend(p.columns().iter().map(|m| time(m.minutes)));
+        row.extend([time(p.totals().minutes), time(p.totals().billable)]);
+        table.push(RowKind::Item, row);
+    }
+    let mut totals = vec!["Totals".to_string()];
+    totals.extend(period.columns().iter().map(|c| time(c.totals().minutes)));
+    totals.extend([time(period.totals().minutes), String::new()]);
+    table.push(RowKind::Total, totals);
+    let mut billable = vec!["Report".to_string()];
+    billable.extend(period.columns().iter().map(|c| time(c.totals().billable)));
+    billable.extend([String::new(), time(period.totals().billable)]);
+    table.push(RowKind::Total, billable);
+    let mut deltas = vec!["Delta".to_string()];
+    deltas.extend(period.columns().iter().map(|c| delta(c.delta_minutes())));
+    deltas.extend([String::new(), delta(period.delta_minutes())]);
+    table.push(RowKind::Delta(period.delta_minutes()), deltas);
+    table
+}
+
+fn totals_table(report: &Report) -> Table {
+    let has_amounts = report.has_amounts();
+    let mut columns = vec![row_heading(report), "Totals", "Report"];
+    if has_amounts {
+        columns.push("Amount");
+    }
+    let mut table = Table::new("Totals", &columns);
+    for p in report.projects.iter() {
+        let mut row = vec![
+            p.short_label(),
+            time(p.totals.minutes),
+            time(p.totals.billable),
+        ];
+        if has_amounts {
+            row.push(amount(p.amounts.as_ref()));
+        }
+        table.push(RowKind::Item, row);
+    }
+    let width = table.columns.len();
+    let mut add_row = |kind: RowKind, label: &str, mut cells: Vec<String>| {
+        let mut row = vec![label.to_string()];
+        row.append(&mut cells);
+        row.resize(width, String::new());
+        table.push(kind, row);
+    };
+    add_row(RowKind::Total, "Totals", vec![time(report.totals.minutes)]);
+    add_row(
+        RowKind::Total,
+        "Report",
+        vec![String::new(), time(report.totals.billable)],
+    );
+    add_row(
+        RowKind::Delta(report.delta_minutes()),
+        "Delta",
+        vec![String::new(), delta(report.delta_minutes())],
+    );
+    table
+}
+
+fn client_table(report: &Report) -> Table {
+    let has_amounts = report.has_amounts();
+    let mut columns = vec!["Client", "Totals", "Report"];
+    if has_amounts {
+        columns.push("Amount");
+    }
+    let mut table = Table::new("Clients", &columns);
+    for c in report.clients.iter() {
+        let mut row = vec![c.label(), time(c.totals.minutes), time(c.totals.billable)];
+        if has_amounts {
+            row.push(amount(c.amounts.as_ref()));
+        }
+        table.push(RowKind::Item, row);
+    }
+    let mut totals = vec![
+        "Totals".to_string(),
+        time(report.totals.minutes),
+        time(report.totals.billable),
+    ];
+    if has_amounts {
+        totals.push(amount(Some(&report.total_amounts)));
+    }
+    table.push(RowKind::Total, totals);
+    table
+}
+
+fn budget_table(report: &Report) -> Table {
+    let mut table = Table::new(
+        "Budgets",
+        &["Budget", "Hours", "Used", "Left", "Pct", "Exhaustion"],
+    );
+    for status in report.budgets.iter() {
+        let remaining = status.remaining_minutes();
+        let left = if remaining < 0 {
+            render_delta(remaining, 1).trim().to_string()
+        } else {
+            time(remaining as u32)
+        };
+        table.push(
+            RowKind::Item,
+            vec![
+                status.budget().pattern().to_string(),
+                time(*status.budget().minutes()),
+                time(*status.used_minutes()),
+                left,
+                format!("{}%", status.percent_used()),
+                status
+                    .exhaustion()
+                    .map_or("-".to_string(), |d| d.to_string()),
+            ],
+        );
+    }
+    table
+}
+
+fn retainer_table(report: &Report) -> Table {
+    let mut table = Table::new(
+        "Retainers",
+        &[
+            "Retainer", "Month", "Hours", "Rolled", "Used", "Left", "Overage",
+        ],
+    );
+    table.text_columns = vec![1];
+    for ledger in report.retainers.iter() {
+        for month in ledger.months() {
+            let first = month.dates().first();
+            table.push(
+                RowKind::Item,
+                vec![
+                    ledger.retainer().client().clone(),
+                    format!("{} {}", month_name(first), first.year()),
+                    time(*month.allotted_minutes()),
+                    time(*month.rolled_over_minutes()),
+                    time(*month.used_minutes()),
+                    time(month.remaining_minutes()),
+                    time(month.overage_minutes()),
+                ],
+            );
+        }
+    }
+    table
+}
+
+/// One row per day with its timeline bar followed by a legend of the
+/// project symbols.
+fn timeline_tables(report: &Report) -> Vec<Table> {
+    let timeline = report.timeline();
+    let hours = format!(
+        "{:02}:00-{:02}:00",
+        timeline.first_hour(),
+        timeline.last_hour()
+    );
+    let mut table = Table::new("Timeline", &["Date", &hours, "Totals", "Report"]);
+    table.bar_columns = vec![1];
+    for day in timeline.days().iter() {
+        table.push(
+            RowKind::Item,
+            vec![
+                format!("{} {}", day.date().day_abbrev(), day.date()),
+                render_bar(report, day),
+                time(day.totals().minutes),
+                time(day.totals().billable),
+            ],
+        );
+    }
+    let mut legend = Table::new("Projects", &["Project", "Symbol"]);
+    legend.bar_columns = vec![1];
+    for (index, project) in report.projects.iter().enumerate() {
+        legend.push(
+            RowKind::Item,
+            vec![project.label(), project_symbol(index).to_string()],
+        );
+    }
+    vec![table, legend]
+}
+
+/// One row per day of the week with each hour of the day shaded by the
+/// minutes worked in it followed by a legend of the shades.
+fn heatmap_tables(report: &Report) -> Vec<Table> {
+    let heatmap = report.heatmap();
+    let mut columns = vec!["Day".to_string()];
+    columns.extend(heatmap.hours().map(|h| format!("{h:02}")));
+    columns.push("Totals".to_string());
+    let columns: Vec<&str> = columns.iter().map(|c| c.as_str()).collect();
+    let mut table = Table::new("Heatmap", &columns);
+    table.text_columns = (1..columns.len() - 1).collect();
+    for row in heatmap.rows().iter() {
+        let mut cells = vec![row.day().to_string()];
+        cells.extend(row.hours().iter().map(|m| {
+            let shade = heatmap.shade(*m);
+            format!("{shade}{shade}")
+        }));
+        cells.push(time(*row.total()));
+        table.push(RowKind::Item, cells);
+    }
+    let mut legend = Table::new("Shades", &["Shade", "Up To"]);
+    for (level, shade) in Heatmap::shades().iter().enumerate().skip(1) {
+        legend.push(
+            RowKind::Item,
+            vec![format!("{shade}{shade}"), time(heatmap.level_limit(level))],
+        );
+    }
+    vec![table, legend]
+}
+
+/// One table per day listing every time range with its duration and the
+/// project's note followed by the totals for the day.
+fn daily_tables(report: &Report) -> Vec<Table> {
+    let mut tables = Vec::new();
+    for day in report.daily().iter() {
+        let mut table = Table::new(
+            &format!("{} {}", day.date().day_abbrev(), day.date()),
+            &["Project", "Time", "Duration", "Note"],
+        );
+        table.text_columns = vec![3];
+        for p in day.projects().iter() {
+            for (index, range) in p.time_ranges().iter().enumerate() {
+                let (label, note) = match index {
+                    0 => (
+                        report.project_label(p.project()),
+                        p.note().clone().unwrap_or_default(),
+                    ),
+                    _ => (String::new(), String::new()),
+                };
+                table.push(
+                    RowKind::Item,
+                    vec![
+                        label,
+                        range.to_string(),
+                        time(range.duration() as u32),
+                        note,
+                    ],
+                );
+            }
+        }
+        table.push(
+            RowKind::Total,
+            vec![
+                "Totals".to_string(),
+                String::new(),
+                time(day.totals().minutes),
+                String::new(),
+            ],
+        );
+        table.push(
+            RowKind::Total,
+            vec![
+                "Report".to_string(),
+                String::new(),
+                time(day.totals().billable),
+                String::new(),
+            ],
+        );
+        tables.push(table);
+    }
+    tables
+}
+
+fn balance_table(status: &BalanceStatus) -> Table {
+    let title = format!("Balance {} - {}", status.balance().from(), status.through());
+    let mut table = Table::new(&title, &["Balance", "Hours"]);
+    table.push(
+        RowKind::Item,
+        vec!["Report".to_string(), time(*status.billable_minutes())],
+    );
+    table.push(
+        RowKind::Item,
+        vec!["Expected".to_string(), time(*status.expected_minutes())],
+    );
+    table.push(
+        RowKind::Delta(status.delta_minutes()),
+        vec!["Delta".to_string(), delta(status.delta_minutes())],
+    );
+    if let Some(overtime) = status.overtime_minutes() {
+        table.push(RowKind::Item, vec!["Overtime".to_string(), time(*overtime)]);
+    }
+    table
+}
+
+fn comparison_table(comparison: &Comparison) -> Table {
+    let title = format!(
+        "Compared With {} {} - {}",
+        comparison.kind().title(),
+        comparison.previous_dates().first(),
+        comparison.previous_dates().last()
+    );
+    let mut table = Table::new(&title, &["Project", "Current", "Previous", "Change", "Pct"]);
+    let cells = |label: String, change: &MinutesChange| {
+        vec![
+            label,
+            time(change.current().minutes),
+            time(change.previous().minutes),
+            delta(change.change_minutes()),
+            change
+                .change_percent()
+                .map_or("-".to_string(), |p| format!("{p:+}%")),
+        ]
+    };
+    for p in comparison.projects().iter() {
+        table.push(RowKind::Item, cells(p.label(), p.change()));
+    }
+    table.push(
+        RowKind::Total,
+        cells("Totals".to_string(), comparison.totals()),
+    );
+    table
+}
+
+fn rounding_table(rounding: &RoundingReport) -> Table {
+    let title = format!("Rounding ({})", rounding.period().name());
+    let mut table = Table::new(&title, &["Period", "Project", "Raw", "Report", "Rounding"]);
+    table.text_columns = vec![1];
+    let cells = |period: &str, label: String, minutes: &Minutes| {
+        vec![
+            period.to_string(),
+            label,
+            time(minutes.minutes),
+            time(minutes.billable),
+            delta(rounding_minutes(minutes)),
+        ]
+    };
+    for period in rounding.periods().iter() {
+        for p in period.projects().iter() {
+            table.push(RowKind::Item, cells(period.label(), p.label(), p.minutes()));
+        }
+        table.push(
+            RowKind::Total,
+            cells(period.label(), "Totals".to_string(), period.totals()),
+        );
+    }
+    for p in rounding.projects().iter() {
+        table.push(RowKind::Item, cells("All", p.label(), p.minutes()));
+    }
+    table.push(
+        RowKind::Total,
+        cells("All", "Totals".to_string(), rounding.totals()),
+    );
+    table
+}
+
+/// Tables holding the same information as the text report.  The time grid
+/// follows the report layout.
+pub fn report_tables(report: &Report) -> Vec<Table> {
+    let mut tables = match report.layout {
+        ReportLayout::Weekly => week_tables(report),
+        ReportLayout::Timeline => timeline_tables(report),
+        ReportLayout::Daily => daily_tables(report),
+        ReportLayout::Heatmap => heatmap_tables(report),
+        _ => report
+            .periods
+            .iter()
+            .map(|p| period_table(report, p))
+            .collect(),
+    };
+    tables.push(totals_table(report));
+    if report.mode != ReportMode::Client {
+        tables.push(client_table(report));
+    }
+    if let Some(balance) = report.balance() {
+        tables.push(balance_table(balance));
+    }
+    if let Some(comparison) = report.comparison() {
+        tables.push(comparison_table(comparison));
+    }
+    if let Some(rounding) = report.rounding() {
+        tables.push(rounding_table(rounding));
+    }
+    if !report.budgets.is_empty() {
+        tables.push(budget_table(report));
+    }
+    if !report.retainers.is_empty() {
+        tables.push(retainer_table(report));
+    }
+    tables
+}
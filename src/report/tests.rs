@@ -0,0 +1,1024 @@
+use super::*;
+use crate::config::ProjectPattern;
+use crate::filter::Filter;
+use crate::model::fixtures::{date, project_times, time_range};
+use crate::rates::Rate;
+use crate::registry::ProjectInfo;
+use crate::retainer::Retainer;
+use crate::target::Target;
+use im::vector;
+
+fn strings(lines: &[&str]) -> Vector<String> {
+    lines.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_compute_project_amounts() {
+    let cms = Project::new("acme", "cms", "");
+    let fix = Project::new("acme", "cms", "fix");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+                project_times(&fix, vector!(time_range(10, 0, 11, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(project_times(&fix, vector!(time_range(8, 0, 10, 0)))),
+            4
+        )
+    );
+    let rates = Rates::new(&vector!(
+        Rate::new(
+            ProjectPattern::new("acme", None, None),
+            10000,
+            "USD",
+            Date::min_date()
+        ),
+        Rate::new(
+            ProjectPattern::new("acme", Some("cms"), Some("fix")),
+            20000,
+            "USD",
+            date(2025, 7, 8)
+        )
+    ));
+
+    let billing = compute_billing_lines(
+        &day_entries,
+        ReportMode::Detail,
+        &rates,
+        &Registry::default(),
+    );
+    assert_eq!(3, billing.len());
+    assert_eq!(
+        vector!(60, 60, 120),
+        billing
+            .iter()
+            .map(|l| *l.billable_minutes())
+            .collect::<Vector<_>>()
+    );
+    let detail = compute_project_amounts(&billing);
+    assert_eq!("100.00 USD", detail[&cms].to_string());
+    assert_eq!("500.00 USD", detail[&fix].to_string());
+
+    let billing = compute_billing_lines(
+        &day_entries,
+        ReportMode::Summary,
+        &rates,
+        &Registry::default(),
+    );
+    assert_eq!(2, billing.len());
+    let summary = compute_project_amounts(&billing);
+    assert_eq!(1, summary.len());
+    assert_eq!("600.00 USD", summary[&cms].to_string());
+    assert_eq!("600.00 USD", total_amounts(&summary).to_string());
+}
+
+#[test]
+fn test_csv_renderer() {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web site", "home");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 4),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+                project_times(&web, vector!(time_range(10, 0, 11, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 10, 5)))),
+            4
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 15));
+    let config = Config::default();
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    let daily = CsvRenderer::new(CsvLayout::Daily).render(&report).unwrap();
+    assert_eq!(
+        strings(&[
+            "date,client,code,subcode,minutes,billable_minutes",
+            "07/04/2025,acme,cms,,70,60",
+            "07/04/2025,acme,web site,home,60,60",
+            "07/07/2025,acme,cms,,125,120"
+        ]),
+        daily
+    );
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Summary,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    let weekly = CsvRenderer::new(CsvLayout::Weekly).render(&report).unwrap();
+    assert_eq!(
+        strings(&[
+            "week,client,code,subcode,minutes,billable_minutes",
+            "06/30/2025,acme,cms,,70,60",
+            "06/30/2025,acme,web site,,60,60",
+            "07/07/2025,acme,cms,,125,120"
+        ]),
+        weekly
+    );
+}
+
+#[test]
+fn test_json_renderer() {
+    let cms = Project::new("acme", "cms", "");
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times(&cms, vector!(time_range(8, 0, 9, 10))).with_note(Some("sync".into()))
+        ),
+        3
+    ));
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 8));
+    let config = Config::default().with_rates(Rates::new(&vector!(Rate::new(
+        ProjectPattern::new("acme", None, None),
+        10000,
+        "USD",
+        Date::min_date()
+    ))));
+    let warnings = vector!("careful".to_string());
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap()
+    .with_warnings(&warnings);
+    let json = JsonRenderer.render(&report).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json[0]).unwrap();
+    assert_eq!("07/07/2025", value["first"]);
+    assert_eq!(70, value["minutes"]);
+    assert_eq!(60, value["billable_minutes"]);
+    assert_eq!(480, value["expected_minutes"]);
+    assert_eq!(-420, value["delta_minutes"]);
+    assert_eq!(10000, value["amounts"]["USD"]);
+    assert_eq!("careful", value["warnings"][0]);
+    assert_eq!("07/07/2025", value["weeks"][0]["monday"]);
+    assert_eq!(1, value["weeks"].as_array().unwrap().len());
+    assert_eq!(2, value["weeks"][0]["days"].as_array().unwrap().len());
+    assert_eq!(60, value["projects"][0]["billable_minutes"]);
+    assert_eq!(10000, value["clients"][0]["amounts"]["USD"]);
+    let entry = &value["entries"][0];
+    assert_eq!(3, entry["line_number"]);
+    assert_eq!("sync", entry["projects"][0]["note"]);
+    assert_eq!("0800", entry["projects"][0]["ranges"][0]["from"]);
+}
+
+#[test]
+fn test_compute_report() {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+                project_times(&web, vector!(time_range(10, 0, 11, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 15),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 10, 5)))),
+            4
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 8), date(2025, 7, 15));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &Config::default(),
+    )
+    .unwrap();
+    assert_eq!(2, report.weeks().len());
+    let week = &report.weeks()[0];
+    assert_eq!(date(2025, 7, 7), *week.monday());
+    assert_eq!(7, week.days().len());
+    assert_eq!(130, *week.days()[1].totals().minutes());
+    assert_eq!(120, *week.days()[1].totals().billable());
+    assert_eq!(2, week.projects().len());
+    assert_eq!(70, *week.projects()[0].days()[1].minutes());
+    assert_eq!(60, *week.projects()[0].totals().billable());
+    assert_eq!(0, *report.weeks()[1].projects()[1].totals().minutes());
+
+    assert_eq!("acme,cms", report.projects()[0].label());
+    assert_eq!(195, *report.projects()[0].totals().minutes());
+    assert_eq!(255, *report.totals().minutes());
+    assert_eq!(255, *report.totals().billable());
+    assert_eq!(960, *report.expected_minutes());
+    assert_eq!(-705, report.delta_minutes());
+    assert!(!report.has_amounts());
+
+    let markdown = MarkdownRenderer.render(&report).unwrap();
+    assert_eq!("# Time Report 07/08/2025 - 07/15/2025", markdown[0]);
+    assert!(
+        markdown.contains(&"| acme,cms | - | 1:10 | - | - | - | - | - | 1:10 | 1:00 |".to_string())
+    );
+    assert!(markdown.contains(&"| **Delta** |  | **-11:45** |".to_string()));
+    let html = HtmlRenderer.render(&report).unwrap();
+    assert!(html.contains(&"<h2>Week of 07/14/2025</h2>".to_string()));
+    assert!(html.contains(&"<style>".to_string()));
+    assert!(
+        html.iter()
+            .any(|l| l.contains(r#"<th class="num weekend">SAT 07/12</th>"#))
+    );
+    assert!(html.contains(
+        &r#"<tr class="total behind"><td>Delta</td><td class="num"></td><td class="num">-11:45</td></tr>"#
+            .to_string()
+    ));
+}
+
+#[test]
+fn test_weekly_targets() {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    let proto = Project::new("bozon", "proto", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 11, 0))),
+                project_times(&web, vector!(time_range(11, 0, 12, 0))),
+                project_times(&proto, vector!(time_range(13, 0, 14, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 15),
+            &vector!(project_times(&web, vector!(time_range(8, 0, 9, 0)))),
+            6
+        )
+    );
+    let config = Config::default().with_targets(vector!(
+        Target::new(
+            ProjectPattern::new("acme", None, None),
+            120,
+            DateRange::new(Date::min_date(), Date::max_date())
+        ),
+        Target::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            120,
+            DateRange::new(Date::min_date(), date(2025, 7, 13))
+        )
+    ));
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 20));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert!(report.has_targets());
+    let week = &report.weeks()[0];
+    let cms_target = week.projects()[0].target().unwrap();
+    assert_eq!(
+        (120, 60, "OVER"),
+        (
+            *cms_target.target_minutes(),
+            cms_target.delta_minutes(),
+            cms_target.marker()
+        )
+    );
+    let web_target = week.projects()[1].target().unwrap();
+    assert_eq!(
+        (-60, "UNDER"),
+        (web_target.delta_minutes(), web_target.marker())
+    );
+    assert_eq!(None, *week.projects()[2].target());
+    assert_eq!(
+        120,
+        *report.weeks()[1].projects()[0]
+            .target()
+            .unwrap()
+            .target_minutes()
+    );
+
+    let text = TextRenderer.render(&report).unwrap();
+    assert!(text[1].ends_with("TOTALS   REPORT   TARGET   DELTA   STATUS"));
+    assert!(text[2].ends_with("3:00     3:00     2:00   +1:00   OVER"));
+    assert!(text[4].ends_with("1:00     1:00"));
+
+    let markdown = MarkdownRenderer.render(&report).unwrap();
+    assert!(
+        markdown.contains(
+            &"| acme,web | - | 1:00 | - | - | - | - | - | 1:00 | 1:00 | 2:00 | -1:00 | UNDER |"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_retainers() {
+    let cms = Project::new("acme", "cms", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 6, 10),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 12, 0)))),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 17, 0)))),
+            4
+        )
+    );
+    let config = Config::default().with_retainers(vector!(Retainer::new(
+        "acme",
+        6 * 60,
+        60,
+        Some(date(2025, 6, 1))
+    )));
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 15));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::parse("client=bozon").unwrap(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(1, report.retainers().len());
+    let months = report.retainers()[0].months();
+    assert_eq!(1, months.len());
+    assert_eq!(60, *months[0].rolled_over_minutes());
+    assert_eq!(540, *months[0].used_minutes());
+    assert_eq!(120, months[0].overage_minutes());
+
+    let text = TextRenderer.render(&report).unwrap();
+    assert_eq!(
+        "acme       July 2025    6:00     1:00   9:00      -      2:00",
+        text[text.len() - 1]
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&JsonRenderer.render(&report).unwrap()[0]).unwrap();
+    let month = &json["retainers"][0]["months"][0];
+    assert_eq!("07/01/2025", month["first"]);
+    assert_eq!(120, month["overage_minutes"]);
+}
+
+#[test]
+fn test_compute_periods() {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 6, 30),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 9, 10)))),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 1),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+                project_times(&web, vector!(time_range(10, 0, 11, 0)))
+            ),
+            4
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(project_times(&web, vector!(time_range(8, 0, 10, 5)))),
+            8
+        )
+    );
+    let dates = DateRange::new(date(2025, 6, 15), date(2025, 7, 31));
+    let config = Config::default();
+
+    let monthly = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Monthly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(2, monthly.periods().len());
+    let june = &monthly.periods()[0];
+    assert_eq!("June 2025", june.title());
+    assert_eq!(
+        vec!["06/15", "06/16", "06/23", "06/30"],
+        june.columns()
+            .iter()
+            .map(|c| c.label().as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(1, june.projects().len());
+    assert_eq!(70, june.totals().minutes);
+    assert_eq!(480, *june.expected_minutes());
+    let july = &monthly.periods()[1];
+    assert_eq!("07/01", july.columns()[0].label());
+    assert_eq!(date(2025, 7, 6), *july.columns()[0].dates().last());
+    assert_eq!(2, july.projects().len());
+    assert_eq!(130, july.columns()[0].totals().minutes);
+    assert_eq!(120, july.columns()[0].totals().billable);
+    assert_eq!(-360, july.columns()[0].delta_minutes());
+    assert_eq!(255, july.totals().minutes);
+    assert_eq!(240, july.totals().billable);
+    assert_eq!(-720, july.delta_minutes());
+
+    let yearly = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Yearly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(1, yearly.periods().len());
+    let year = &yearly.periods()[0];
+    assert_eq!("2025", year.title());
+    assert_eq!(2, year.columns().len());
+    assert_eq!("JUL", year.columns()[1].label());
+    assert_eq!(
+        vector!(0, 180),
+        year.projects()[1]
+            .columns()
+            .iter()
+            .map(|m| m.billable)
+            .collect::<Vector<_>>()
+    );
+    assert_eq!(325, year.totals().minutes);
+    assert_eq!(1440, *year.expected_minutes());
+
+    let markdown = MarkdownRenderer.render(&yearly).unwrap();
+    assert!(markdown.contains(&"| acme,web | - | 3:05 | 3:05 | 3:00 |".to_string()));
+    assert!(
+        markdown.contains(&"| **Delta** | **-7:00** | **-12:00** |  | **-19:00** |".to_string())
+    );
+    assert!(
+        compute_report(
+            dates,
+            &day_entries,
+            ReportMode::Detail,
+            ReportLayout::Weekly,
+            &Filter::default(),
+            &config
+        )
+        .unwrap()
+        .periods()
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_client_mode() {
+    let cms = Project::new("acme", "cms", "");
+    let fix = Project::new("acme", "cms", "fix");
+    let internal = Project::new("acme", "internal", "");
+    let proto = Project::new("bozon", "proto", "");
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+            project_times(&fix, vector!(time_range(10, 0, 11, 0))),
+            project_times(&internal, vector!(time_range(11, 0, 12, 0))),
+            project_times(&proto, vector!(time_range(13, 0, 14, 5)))
+        ),
+        1
+    ));
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 13));
+    let config = Config::default()
+        .with_registry(Registry::new(&vector!(
+            ProjectInfo::new(
+                ProjectPattern::new("acme", None, None),
+                "Acme Corporation",
+                DateRange::new(Date::min_date(), Date::max_date()),
+                true
+            ),
+            ProjectInfo::new(
+                ProjectPattern::new("acme", Some("internal"), None),
+                "Internal",
+                DateRange::new(Date::min_date(), Date::max_date()),
+                false
+            )
+        )))
+        .with_rates(Rates::new(&vector!(Rate::new(
+            ProjectPattern::new("acme", None, None),
+            10000,
+            "USD",
+            Date::min_date()
+        ))));
+
+    let detail = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(4, detail.projects().len());
+    assert_eq!(2, detail.clients().len());
+    let acme = &detail.clients()[0];
+    assert_eq!("Acme Corporation", acme.label());
+    assert_eq!(190, acme.totals().minutes);
+    assert_eq!(120, acme.totals().billable);
+    assert_eq!("200.00 USD", acme.amounts().as_ref().unwrap().to_string());
+    assert_eq!("bozon", detail.clients()[1].label());
+    assert_eq!(None, *detail.clients()[1].amounts());
+
+    let client = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Client,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["Acme Corporation", "bozon"],
+        client
+            .projects()
+            .iter()
+            .map(|p| p.label())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(190, client.projects()[0].totals().minutes);
+    assert_eq!(120, client.projects()[0].totals().billable);
+    assert_eq!(
+        "200.00 USD",
+        client.projects()[0].amounts().as_ref().unwrap().to_string()
+    );
+    assert_eq!(255, client.totals().minutes);
+    assert_eq!(180, client.totals().billable);
+    let text = TextRenderer.render(&client).unwrap();
+    assert!(text[1].starts_with("CLIENT"));
+    assert_eq!(2, text.iter().filter(|l| l.starts_with("CLIENT")).count());
+    let text = TextRenderer.render(&detail).unwrap();
+    assert!(text.contains(&"Acme Corporation     3:10     2:00   200.00 USD".to_string()));
+}
+
+#[test]
+fn test_summary_mode_nonbillable_subcode() {
+    let cms = Project::new("acme", "cms", "");
+    let fix = Project::new("acme", "cms", "fix");
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+            project_times(&fix, vector!(time_range(10, 0, 11, 0)))
+        ),
+        1
+    ));
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 13));
+    let config = Config::default().with_registry(Registry::new(&vector!(ProjectInfo::new(
+        ProjectPattern::new("acme", Some("cms"), Some("fix")),
+        "Fixes",
+        DateRange::new(Date::min_date(), Date::max_date()),
+        false
+    ))));
+    let summary = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Summary,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(1, summary.projects().len());
+    assert_eq!(130, summary.projects()[0].totals().minutes);
+    assert_eq!(60, summary.projects()[0].totals().billable);
+    assert_eq!(60, summary.totals().billable);
+}
+
+#[test]
+fn test_compute_timeline() {
+    let cms = Project::new("acme", "cms", "");
+    let proto = Project::new("bozon", "proto", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(&cms, vector!(time_range(7, 30, 9, 10))),
+                project_times(&proto, vector!(time_range(9, 15, 10, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(project_times(&proto, vector!(time_range(17, 0, 18, 20)))),
+            5
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 13));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Timeline,
+        &Filter::default(),
+        &Config::default(),
+    )
+    .unwrap();
+    let timeline = report.timeline();
+    assert_eq!(7, *timeline.first_hour());
+    assert_eq!(19, *timeline.last_hour());
+    assert_eq!(48, timeline.slot_count());
+    assert_eq!(2, timeline.days().len());
+    let monday = &timeline.days()[0];
+    assert_eq!(145, monday.totals().minutes);
+    assert_eq!(135, monday.totals().billable);
+    assert_eq!(
+        "..AAAAAAABBB....................................",
+        timeline::render_bar(&report, monday)
+    );
+    assert_eq!(
+        "........................................BBBBB...",
+        timeline::render_bar(&report, &timeline.days()[1])
+    );
+
+    let text = TextRenderer.render(&report).unwrap();
+    assert!(text[1].starts_with("DATE             07:00-19:00"));
+    assert!(text.contains(&"acme,cms      A".to_string()));
+    assert!(text.contains(&"bozon,proto   B".to_string()));
+    assert!(
+        text.contains(
+            &"TUE 07/08/2025   ........................................BBBBB...     1:20     1:15"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_daily_layout() {
+    let cms = Project::new("acme", "cms", "");
+    let internal = Project::new("acme", "internal", "");
+    let day_entries = vector!(DayEntry::new(
+        date(2025, 7, 7),
+        &vector!(
+            project_times(
+                &cms,
+                vector!(time_range(8, 0, 9, 10), time_range(13, 0, 13, 20))
+            )
+            .with_note(Some("release <v2> | *hot_fix*".to_string())),
+            project_times(&internal, vector!(time_range(9, 10, 10, 0)))
+        ),
+        1
+    ));
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 13));
+    let config = Config::default().with_registry(Registry::new(&vector!(ProjectInfo::new(
+        ProjectPattern::new("acme", Some("internal"), None),
+        "Internal",
+        DateRange::new(Date::min_date(), Date::max_date()),
+        false
+    ))));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Daily,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(1, report.daily().len());
+    let day = &report.daily()[0];
+    assert_eq!(2, day.projects().len());
+    assert_eq!(140, day.totals().minutes);
+    assert_eq!(90, day.totals().billable);
+
+    let text = TextRenderer.render(&report).unwrap();
+    assert_eq!(
+        strings(&[
+            "MON 07/07/2025",
+            "PROJECT         TIME   DURATION   NOTE",
+            "acme,cms   0800-0910       1:10   release <v2> | *hot_fix*",
+            "           1300-1320       0:20",
+            "Internal   0910-1000       0:50",
+            "Totals                     2:20",
+            "Report                     1:30",
+        ]),
+        text.take(7)
+    );
+
+    let markdown = MarkdownRenderer.render(&report).unwrap();
+    assert!(markdown.contains(
+        &"| acme,cms | 0800-0910 | 1:10 | release &lt;v2&gt; \\| \\*hot\\_fix\\* |".to_string()
+    ));
+}
+
+#[test]
+fn test_heatmap_layout() {
+    let cms = Project::new("acme", "cms", "");
+    let proto = Project::new("bozon", "proto", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(&cms, vector!(time_range(7, 30, 9, 10))),
+                project_times(&proto, vector!(time_range(9, 15, 10, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 14),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 8, 30)))),
+            5
+        ),
+        DayEntry::new(
+            date(2025, 7, 16),
+            &vector!(project_times(&proto, vector!(time_range(17, 0, 18, 20)))),
+            8
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 20));
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Heatmap,
+        &Filter::default(),
+        &Config::default(),
+    )
+    .unwrap();
+    let heatmap = report.heatmap();
+    assert_eq!(7, *heatmap.first_hour());
+    assert_eq!(19, *heatmap.last_hour());
+    assert_eq!(7, heatmap.rows().len());
+    let monday = &heatmap.rows()[0];
+    assert_eq!("MON", monday.day());
+    assert_eq!(
+        vector!(30, 90, 55, 0, 0, 0, 0, 0, 0, 0, 0, 0),
+        monday.hours().clone()
+    );
+    assert_eq!(175, *monday.total());
+    assert_eq!(
+        vector!(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 60, 20),
+        heatmap.rows()[2].hours().clone()
+    );
+    assert_eq!(90, heatmap.max_minutes());
+    assert_eq!(4, heatmap.level(90));
+    assert_eq!(1, heatmap.level(20));
+
+    let text = TextRenderer.render(&report).unwrap();
+    assert_eq!(
+        "DAY   07   08   09   10   11   12   13   14   15   16   17   18   TOTALS",
+        text[1]
+    );
+    assert_eq!(
+        "MON   ▒▒   ██   ▓▓   ..   ..   ..   ..   ..   ..   ..   ..   ..     2:55",
+        text[2]
+    );
+    assert!(text.contains(&"██       1:30".to_string()));
+
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Heatmap,
+        &Filter::parse("client=bozon").unwrap(),
+        &Config::default(),
+    )
+    .unwrap();
+    let heatmap = report.heatmap();
+    assert_eq!(45, *heatmap.rows()[0].total());
+    assert_eq!(80, *heatmap.rows()[2].total());
+}
+
+#[test]
+fn test_comparison_dates() {
+    let shift = |kind: ComparisonKind, first: Date, last: Date| {
+        kind.shift(&DateRange::new(first, last)).unwrap()
+    };
+    assert_eq!(
+        DateRange::new(date(2025, 6, 16), date(2025, 6, 30)),
+        shift(
+            ComparisonKind::Previous,
+            date(2025, 7, 1),
+            date(2025, 7, 15)
+        )
+    );
+    assert_eq!(
+        DateRange::new(date(2025, 2, 1), date(2025, 2, 15)),
+        shift(
+            ComparisonKind::Previous,
+            date(2025, 2, 16),
+            date(2025, 2, 28)
+        )
+    );
+    assert_eq!(
+        DateRange::new(date(2025, 2, 1), date(2025, 2, 28)),
+        shift(
+            ComparisonKind::Previous,
+            date(2025, 3, 1),
+            date(2025, 3, 31)
+        )
+    );
+    assert_eq!(
+        DateRange::new(date(2025, 6, 30), date(2025, 7, 6)),
+        shift(
+            ComparisonKind::Previous,
+            date(2025, 7, 7),
+            date(2025, 7, 13)
+        )
+    );
+    assert_eq!(
+        DateRange::new(date(2024, 2, 16), date(2024, 2, 29)),
+        shift(
+            ComparisonKind::LastYear,
+            date(2025, 2, 16),
+            date(2025, 2, 28)
+        )
+    );
+}
+
+#[test]
+fn test_compute_comparison() {
+    let cms = Project::new("acme", "cms", "");
+    let proto = Project::new("bozon", "proto", "");
+    let web = Project::new("acme", "web", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 6, 30),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 12, 0))),
+                project_times(&web, vector!(time_range(13, 0, 14, 0)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 13, 0))),
+                project_times(&proto, vector!(time_range(13, 0, 14, 30)))
+            ),
+            5
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 7), date(2025, 7, 13));
+    let config = Config::default();
+    let comparison = compute_comparison(
+        dates,
+        ComparisonKind::Previous,
+        &day_entries,
+        ReportMode::Detail,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(
+        DateRange::new(date(2025, 6, 30), date(2025, 7, 6)),
+        *comparison.previous_dates()
+    );
+    let changes: Vec<(String, u32, u32, i32, Option<i32>)> = comparison
+        .projects()
+        .iter()
+        .map(|p| {
+            (
+                p.label(),
+                p.change().current().minutes,
+                p.change().previous().minutes,
+                p.change().change_minutes(),
+                p.change().change_percent(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("acme,cms".to_string(), 300, 240, 60, Some(25)),
+            ("acme,web".to_string(), 0, 60, -60, Some(-100)),
+            ("bozon,proto".to_string(), 90, 0, 90, None),
+        ],
+        changes
+    );
+    assert_eq!(Some(30), comparison.totals().change_percent());
+
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Detail,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap()
+    .with_comparison(comparison);
+    let text = TextRenderer.render(&report).unwrap();
+    assert!(text.contains(&"COMPARED WITH PREVIOUS PERIOD 06/30/2025 - 07/06/2025".to_string()));
+    assert!(text.contains(&"acme,web            -       1:00    -1:00   -100%".to_string()));
+    assert!(text.contains(&"Totals           6:30       5:00    +1:30    +30%".to_string()));
+}
+
+#[test]
+fn test_compute_rounding() {
+    let cms = Project::new("acme", "cms", "");
+    let fix = Project::new("acme", "cms", "fix");
+    let internal = Project::new("acme", "internal", "");
+    let day_entries = vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(&cms, vector!(time_range(8, 0, 9, 10))),
+                project_times(&fix, vector!(time_range(9, 10, 9, 20))),
+                project_times(&internal, vector!(time_range(10, 0, 10, 7)))
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 8),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 8, 50)))),
+            5
+        ),
+        DayEntry::new(
+            date(2025, 7, 14),
+            &vector!(project_times(&cms, vector!(time_range(8, 0, 9, 0)))),
+            9
+        )
+    );
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 31));
+    let config = Config::default().with_registry(Registry::new(&vector!(ProjectInfo::new(
+        ProjectPattern::new("acme", Some("internal"), None),
+        "Internal",
+        DateRange::new(Date::min_date(), Date::max_date()),
+        false
+    ))));
+    let rounding = compute_rounding(
+        dates,
+        RoundingPeriod::Weekly,
+        &day_entries,
+        ReportMode::Summary,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap();
+    let periods: Vec<(String, DateRange, u32, u32)> = rounding
+        .periods()
+        .iter()
+        .map(|p| {
+            (
+                p.label().clone(),
+                *p.dates(),
+                p.totals().minutes,
+                p.totals().billable,
+            )
+        })
+        .collect();
+    // The fix subcode is combined with cms before rounding in summary mode.
+    assert_eq!(
+        vec![
+            (
+                "Week of 07/07/2025".to_string(),
+                DateRange::new(date(2025, 7, 7), date(2025, 7, 8)),
+                130,
+                120
+            ),
+            (
+                "Week of 07/14/2025".to_string(),
+                DateRange::new(date(2025, 7, 14), date(2025, 7, 14)),
+                60,
+                60
+            ),
+        ],
+        periods
+    );
+    assert_eq!(1, rounding.projects().len());
+    assert_eq!(-10, rounding::rounding_minutes(rounding.totals()));
+
+    let report = compute_report(
+        dates,
+        &day_entries,
+        ReportMode::Summary,
+        ReportLayout::Weekly,
+        &Filter::default(),
+        &config,
+    )
+    .unwrap()
+    .with_rounding(rounding);
+    let text = TextRenderer.render(&report).unwrap();
+    assert!(text.contains(&"ROUNDING (WEEKLY)".to_string()));
+    assert!(text.contains(&"Week of 07/07/2025   acme,cms   2:10     2:00      -0:10".to_string()));
+    assert!(text.contains(&"All                  Totals     3:10     3:00      -0:10".to_string()));
+}
@@ -0,0 +1,58 @@
+use super::table::{Table, report_tables};
+use super::{Report, ReportRenderer};
+use anyhow::Result;
+use im::Vector;
+
+/// Renders the report as fixed width text for the terminal.  Each table is
+/// headed by its title in capitals and tables are separated by a blank line.
+pub struct TextRenderer;
+
+const COLUMN_PAD: usize = 3;
+
+/// Lines of a table with every column as wide as its widest cell.  The label,
+/// text and bar columns are left aligned and the others right aligned.
+fn render_table(table: &Table) -> Vector<String> {
+    let columns: Vec<String> = table.columns.iter().map(|c| c.to_uppercase()).collect();
+    let mut widths: Vec<usize> = columns.iter().map(|c| c.chars().count()).collect();
+    for row in table.rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.cells.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let left_aligned =
+        |i: usize| i == 0 || table.text_columns.contains(&i) || table.bar_columns.contains(&i);
+    let render_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(i, (cell, width))| match left_aligned(i) {
+                true => format!("{cell:<width$}"),
+                false => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join(&" ".repeat(COLUMN_PAD))
+            .trim_end()
+            .to_string()
+    };
+    let mut answer = Vector::new();
+    answer.push_back(table.title.to_uppercase());
+    answer.push_back(render_row(&columns));
+    for row in table.rows.iter() {
+        answer.push_back(render_row(&row.cells));
+    }
+    answer
+}
+
+impl ReportRenderer for TextRenderer {
+    fn render(&self, report: &Report) -> Result<Vector<String>> {
+        let mut answer = Vector::new();
+        for table in report_tables(report) {
+            if !answer.is_empty() {
+                answer.push_back("".to_string());
+            }
+            answer.append(render_table(&table));
+        }
+        Ok(answer)
+    }
+}
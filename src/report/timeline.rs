@@ -0,0 +1,145 @@
+use super::{BillableMinutes, Minutes, Report, day_totals};
+use crate::model::{Date, DayEntry, Project, TimeRange};
+use derive_getters::Getters;
+use im::Vector;
+
+/// Minutes covered by each position of a timeline bar.
+pub const SLOT_MINUTES: u16 = 15;
+
+const DEFAULT_FIRST_HOUR: u16 = 8;
+const DEFAULT_LAST_HOUR: u16 = 18;
+
+const SYMBOLS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Character drawn in text bars for the project at an index in the report's
+/// project list.  Projects beyond the available symbols share `#`.
+pub fn project_symbol(index: usize) -> char {
+    SYMBOLS.chars().nth(index).unwrap_or('#')
+}
+
+/// Index of the project drawn with a symbol or `None` for empty slots and `#`.
+pub fn symbol_index(symbol: char) -> Option<usize> {
+    SYMBOLS.find(symbol)
+}
+
+/// One range of time worked on a project.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct TimelineRange {
+    project: Project,
+    range: TimeRange,
+}
+
+/// Every range worked on one date in order of starting time.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct TimelineDay {
+    date: Date,
+    ranges: Vector<TimelineRange>,
+    totals: Minutes,
+}
+
+impl TimelineDay {
+    /// Project worked at the middle of each slot between the first and last
+    /// hour of the timeline.
+    pub fn slots(&self, timeline: &Timeline) -> Vector<Option<Project>> {
+        (0..timeline.slot_count())
+            .map(|slot| {
+                let middle =
+                    timeline.first_hour * 60 + slot as u16 * SLOT_MINUTES + SLOT_MINUTES / 2;
+                self.ranges
+                    .iter()
+                    .find(|r| {
+                        r.range.from().minute_of_day() <= middle
+                            && middle < r.range.to().minute_of_day()
+                    })
+                    .map(|r| r.project.clone())
+            })
+            .collect()
+    }
+}
+
+/// Ranges worked on each date with time recorded.  All days share the same
+/// hours so their bars line up.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Timeline {
+    first_hour: u16,
+    last_hour: u16,
+    days: Vector<TimelineDay>,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Timeline {
+            first_hour: DEFAULT_FIRST_HOUR,
+            last_hour: DEFAULT_LAST_HOUR,
+            days: Vector::new(),
+        }
+    }
+}
+
+impl Timeline {
+    pub fn slot_count(&self) -> usize {
+        ((self.last_hour - self.first_hour) * 60 / SLOT_MINUTES) as usize
+    }
+
+    /// Hours at the start of each group of slots making up one hour.
+    pub fn hours(&self) -> impl Iterator<Item = u16> {
+        self.first_hour..self.last_hour
+    }
+
+    pub fn slots_per_hour(&self) -> usize {
+        (60 / SLOT_MINUTES) as usize
+    }
+}
+
+/// Computes the timeline for day entries already limited to the report dates
+/// and adjusted for the report mode.  The hours run from 8:00 to 18:00 widened
+/// as needed to include every range.
+pub fn compute_timeline(day_entries: &Vector<DayEntry>, billable: &BillableMinutes) -> Timeline {
+    let days: Vector<TimelineDay> = day_entries
+        .iter()
+        .map(|entry| {
+            let mut ranges: Vector<TimelineRange> = entry
+                .projects()
+                .iter()
+                .flat_map(|p| {
+                    p.time_ranges().iter().map(|r| TimelineRange {
+                        project: p.project().clone(),
+                        range: *r,
+                    })
+                })
+                .collect();
+            ranges.sort_by(|a, b| a.range.cmp(&b.range));
+            TimelineDay {
+                date: *entry.date(),
+                ranges,
+                totals: day_totals(entry, billable),
+            }
+        })
+        .collect();
+    let ranges = || days.iter().flat_map(|d| d.ranges.iter().map(|r| r.range));
+    let first_hour = ranges()
+        .map(|r| r.from().hour())
+        .min()
+        .map_or(DEFAULT_FIRST_HOUR, |h| h.min(DEFAULT_FIRST_HOUR));
+    let last_hour = ranges()
+        .map(|r| r.to().minute_of_day().div_ceil(60))
+        .max()
+        .map_or(DEFAULT_LAST_HOUR, |h| h.max(DEFAULT_LAST_HOUR));
+    Timeline {
+        first_hour,
+        last_hour,
+        days,
+    }
+}
+
+/// Text bar for one day with the symbol of the project worked in each slot
+/// and `.` for slots without time.
+pub fn render_bar(report: &Report, day: &TimelineDay) -> String {
+    day.slots(&report.timeline)
+        .iter()
+        .map(|slot| match slot {
+            Some(project) => report.project_index(project).map_or('#', project_symbol),
+            None => '.',
+        })
+        .collect()
+}
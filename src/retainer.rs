@@ -0,0 +1,142 @@
+use crate::model::{Date, DateRange, DayEntry};
+use crate::registry::Registry;
+use crate::report::day_billable_minutes;
+use anyhow::Result;
+use derive_getters::Getters;
+use im::{OrdMap, Vector};
+
+#[cfg(test)]
+mod tests;
+
+/// Hours a client prepays each month along with the most unused hours that
+/// roll over into the next month.  Without a start date the retainer starts
+/// in the first month with time recorded for the client.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Retainer {
+    client: String,
+    monthly_minutes: u32,
+    rollover_cap_minutes: u32,
+    from: Option<Date>,
+}
+
+impl Retainer {
+    pub fn new(
+        client: &str,
+        monthly_minutes: u32,
+        rollover_cap_minutes: u32,
+        from: Option<Date>,
+    ) -> Self {
+        Retainer {
+            client: client.to_string(),
+            monthly_minutes,
+            rollover_cap_minutes,
+            from,
+        }
+    }
+}
+
+/// Hours allotted, rolled over from the month before and used in one month.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct RetainerMonth {
+    dates: DateRange,
+    allotted_minutes: u32,
+    rolled_over_minutes: u32,
+    used_minutes: u32,
+}
+
+impl RetainerMonth {
+    pub fn available_minutes(&self) -> u32 {
+        self.allotted_minutes + self.rolled_over_minutes
+    }
+
+    /// Unused hours at the end of the month before applying the rollover cap.
+    pub fn remaining_minutes(&self) -> u32 {
+        self.available_minutes().saturating_sub(self.used_minutes)
+    }
+
+    /// Hours used beyond those available, billed on top of the retainer.
+    pub fn overage_minutes(&self) -> u32 {
+        self.used_minutes.saturating_sub(self.available_minutes())
+    }
+}
+
+/// Months of a retainer from its start through the month of a given date.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct RetainerLedger {
+    retainer: Retainer,
+    months: Vector<RetainerMonth>,
+}
+
+impl RetainerLedger {
+    /// The ledger limited to the months overlapping a date range.
+    pub fn within(&self, dates: &DateRange) -> Self {
+        RetainerLedger {
+            retainer: self.retainer.clone(),
+            months: self
+                .months
+                .iter()
+                .filter(|m| m.dates.first() <= dates.last() && m.dates.last() >= dates.first())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The calendar month holding a date.
+fn month_dates(date: &Date) -> Result<DateRange> {
+    let mut last = *date;
+    while !last.is_month_end() {
+        last = last.next()?;
+    }
+    Ok(DateRange::new(
+        Date::new(*date.year(), *date.month(), 1)?,
+        last,
+    ))
+}
+
+/// Computes the ledger through the month holding a date.  Each month's unused
+/// hours roll over into the next month up to the cap and hours used beyond
+/// those available are overage.  The first month starts on the retainer's
+/// start date.
+pub fn compute_retainer_ledger(
+    retainer: &Retainer,
+    day_entries: &Vector<DayEntry>,
+    registry: &Registry,
+    through: Date,
+) -> Result<RetainerLedger> {
+    let mut used = OrdMap::<Date, u32>::new();
+    for entry in day_entries.iter().filter(|e| *e.date() <= through) {
+        let minutes = day_billable_minutes(entry, |p| {
+            *p.client() == retainer.client && registry.is_billable(p)
+        });
+        if minutes > 0 {
+            *used.entry(*entry.date()).or_default() += minutes;
+        }
+    }
+    let first = retainer.from.or_else(|| used.keys().next().copied());
+    let mut months = Vector::new();
+    let mut month = first.map(|d| month_dates(&d)).transpose()?;
+    if let (Some(from), Some(dates)) = (retainer.from, month) {
+        // A retainer starting mid-month only counts time from its start.
+        month = Some(DateRange::new(from, *dates.last()));
+    }
+    let mut rolled_over_minutes = 0;
+    while let Some(dates) = month.filter(|m| *m.first() <= through) {
+        let entry = RetainerMonth {
+            dates,
+            allotted_minutes: retainer.monthly_minutes,
+            rolled_over_minutes,
+            used_minutes: used
+                .range(dates.first()..=dates.last())
+                .map(|(_, m)| m)
+                .sum(),
+        };
+        rolled_over_minutes = entry.remaining_minutes().min(retainer.rollover_cap_minutes);
+        months.push_back(entry);
+        month = Some(month_dates(&dates.last().next()?)?);
+    }
+    Ok(RetainerLedger {
+        retainer: retainer.clone(),
+        months,
+    })
+}
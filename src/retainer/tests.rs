@@ -0,0 +1,94 @@
+use super::*;
+use crate::model::Project;
+use crate::model::fixtures::{date, project_times, time_range};
+use im::vector;
+
+fn entry(month: u8, day: u8, client: &str, hours: u16) -> DayEntry {
+    let project = Project::new(client, "cms", "");
+    let times = project_times(&project, vector!(time_range(8, 0, 8 + hours, 0)));
+    DayEntry::new(date(2025, month, day), &vector!(times), 0)
+}
+
+#[test]
+fn test_retainer_ledger() {
+    let day_entries = vector!(
+        entry(5, 10, "acme", 4),
+        entry(5, 10, "bozon", 8),
+        entry(6, 10, "acme", 9),
+        entry(6, 10, "acme", 9),
+        entry(8, 10, "acme", 2)
+    );
+    let retainer = Retainer::new("acme", 10 * 60, 5 * 60, None);
+    let ledger = compute_retainer_ledger(
+        &retainer,
+        &day_entries,
+        &Registry::default(),
+        date(2025, 8, 5),
+    )
+    .unwrap();
+    let months: Vec<(u8, u32, u32, u32, u32)> = ledger
+        .months
+        .iter()
+        .map(|m| {
+            (
+                *m.dates.first().month(),
+                m.rolled_over_minutes / 60,
+                m.used_minutes / 60,
+                m.remaining_minutes() / 60,
+                m.overage_minutes() / 60,
+            )
+        })
+        .collect();
+    // May leaves 6 hours of which 5 roll over, June runs over by 3 hours and
+    // the time on 08/10 is after the ledger ends.
+    assert_eq!(
+        vec![
+            (5, 0, 4, 6, 0),
+            (6, 5, 18, 0, 3),
+            (7, 0, 0, 10, 0),
+            (8, 5, 0, 15, 0)
+        ],
+        months
+    );
+    assert_eq!(
+        DateRange::new(date(2025, 6, 1), date(2025, 6, 30)),
+        ledger.months[1].dates
+    );
+
+    let ledger = ledger.within(&DateRange::new(date(2025, 6, 16), date(2025, 7, 15)));
+    assert_eq!(2, ledger.months.len());
+
+    let retainer = Retainer::new("bozon", 600, 0, Some(date(2025, 7, 1)));
+    let ledger = compute_retainer_ledger(
+        &retainer,
+        &day_entries,
+        &Registry::default(),
+        date(2025, 6, 30),
+    )
+    .unwrap();
+    assert!(ledger.months.is_empty());
+}
+
+#[test]
+fn test_retainer_mid_month_start() {
+    let day_entries = vector!(
+        entry(5, 20, "acme", 4),
+        entry(6, 10, "acme", 3),
+        entry(6, 20, "acme", 2)
+    );
+    let retainer = Retainer::new("acme", 10 * 60, 0, Some(date(2025, 6, 15)));
+    let ledger = compute_retainer_ledger(
+        &retainer,
+        &day_entries,
+        &Registry::default(),
+        date(2025, 6, 30),
+    )
+    .unwrap();
+    // Only the time on 06/20 counts towards the first month.
+    assert_eq!(1, ledger.months.len());
+    assert_eq!(
+        DateRange::new(date(2025, 6, 15), date(2025, 6, 30)),
+        ledger.months[0].dates
+    );
+    assert_eq!(2 * 60, ledger.months[0].used_minutes);
+}
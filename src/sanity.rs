@@ -0,0 +1,118 @@
+use crate::check::{Diagnostic, Severity};
+use crate::model::{Date, DayEntry, Time, format_hours};
+use derive_getters::Getters;
+use im::Vector;
+
+#[cfg(test)]
+mod tests;
+
+/// Something implausible about a date block that is probably a typo.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SanityRule {
+    /// More than this many minutes recorded on one date.
+    MaxDayMinutes(u32),
+    /// A single range longer than this many minutes.
+    MaxRangeMinutes(u32),
+    /// A range starting before this time.
+    StartBefore(Time),
+    /// A range ending after this time.
+    EndAfter(Time),
+    /// A date after today.
+    Future,
+    /// A date block with no time lines.
+    Empty,
+}
+
+/// A rule along with how serious it is when it fails.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Getters)]
+pub struct SanityCheck {
+    rule: SanityRule,
+    severity: Severity,
+}
+
+impl SanityCheck {
+    pub fn new(rule: SanityRule, severity: Severity) -> Self {
+        SanityCheck { rule, severity }
+    }
+
+    fn diagnostic(&self, entry: &DayEntry, kind: &str, detail: String) -> Diagnostic {
+        Diagnostic::new(self.severity, kind, Some(*entry.line_number()), &detail)
+    }
+
+    /// Diagnostics for each way the date block fails the rule.
+    fn apply(&self, entry: &DayEntry, today: Date) -> Vector<Diagnostic> {
+        let date = entry.date();
+        let ranges = entry.projects().iter().flat_map(|p| p.time_ranges().iter());
+        match self.rule {
+            SanityRule::MaxDayMinutes(limit) => {
+                let minutes: u32 = entry.projects().iter().map(|p| p.total_minutes()).sum();
+                (minutes > limit)
+                    .then(|| {
+                        self.diagnostic(
+                            entry,
+                            "long day",
+                            format!(
+                                "{date} has {} hours (limit {})",
+                                format_hours(minutes),
+                                format_hours(limit)
+                            ),
+                        )
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            SanityRule::MaxRangeMinutes(limit) => ranges
+                .filter(|r| r.duration() as u32 > limit)
+                .map(|r| {
+                    self.diagnostic(
+                        entry,
+                        "long range",
+                        format!(
+                            "{date} {r} is {} hours (limit {})",
+                            format_hours(r.duration() as u32),
+                            format_hours(limit)
+                        ),
+                    )
+                })
+                .collect(),
+            SanityRule::StartBefore(time) => ranges
+                .filter(|r| *r.from() < time)
+                .map(|r| {
+                    self.diagnostic(
+                        entry,
+                        "early start",
+                        format!("{date} {r} starts before {time}"),
+                    )
+                })
+                .collect(),
+            SanityRule::EndAfter(time) => ranges
+                .filter(|r| *r.to() > time)
+                .map(|r| {
+                    self.diagnostic(entry, "late end", format!("{date} {r} ends after {time}"))
+                })
+                .collect(),
+            SanityRule::Future => (*date > today)
+                .then(|| self.diagnostic(entry, "future date", format!("{date} is after {today}")))
+                .into_iter()
+                .collect(),
+            SanityRule::Empty => entry
+                .projects()
+                .is_empty()
+                .then(|| self.diagnostic(entry, "empty date", format!("{date} has no time lines")))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Runs every check on every date block in order of line number.
+pub fn sanity_diagnostics(
+    checks: &Vector<SanityCheck>,
+    day_entries: &Vector<DayEntry>,
+    today: Date,
+) -> Vector<Diagnostic> {
+    day_entries
+        .iter()
+        .flat_map(|e| checks.iter().flat_map(move |c| c.apply(e, today)))
+        .collect()
+}
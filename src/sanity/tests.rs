@@ -0,0 +1,56 @@
+use super::*;
+use crate::model::fixtures::{date, project_times, time, time_range};
+use crate::model::{Project, TimeRange};
+use im::vector;
+
+fn entry(day: u8, ranges: Vector<TimeRange>) -> DayEntry {
+    let projects = if ranges.is_empty() {
+        Vector::new()
+    } else {
+        vector!(project_times(&Project::new("acme", "cms", ""), ranges))
+    };
+    DayEntry::new(date(2025, 7, day), &projects, day as u32 * 10)
+}
+
+#[test]
+fn test_sanity_diagnostics() {
+    let day_entries = vector!(
+        entry(
+            7,
+            vector!(time_range(5, 30, 12, 0), time_range(13, 0, 23, 0))
+        ),
+        entry(8, Vector::new()),
+        entry(9, vector!(time_range(8, 0, 9, 0)))
+    );
+    let checks = vector!(
+        SanityCheck::new(SanityRule::MaxDayMinutes(12 * 60), Severity::Error),
+        SanityCheck::new(SanityRule::MaxRangeMinutes(8 * 60), Severity::Warning),
+        SanityCheck::new(SanityRule::StartBefore(time(6, 0)), Severity::Warning),
+        SanityCheck::new(SanityRule::EndAfter(time(22, 0)), Severity::Warning),
+        SanityCheck::new(SanityRule::Future, Severity::Error),
+        SanityCheck::new(SanityRule::Empty, Severity::Warning)
+    );
+    let diagnostics = sanity_diagnostics(&checks, &day_entries, date(2025, 7, 8));
+    assert_eq!(
+        vector!(
+            "long day:70: 07/07/2025 has 16:30 hours (limit 12:00)".to_string(),
+            "long range:70: 07/07/2025 1300-2300 is 10:00 hours (limit 8:00)".to_string(),
+            "early start:70: 07/07/2025 0530-1200 starts before 0600".to_string(),
+            "late end:70: 07/07/2025 1300-2300 ends after 2200".to_string(),
+            "empty date:80: 07/08/2025 has no time lines".to_string(),
+            "future date:90: 07/09/2025 is after 07/08/2025".to_string()
+        ),
+        diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vector<_>>()
+    );
+    assert_eq!(
+        2,
+        diagnostics
+            .iter()
+            .filter(|d| *d.severity() == Severity::Error)
+            .count()
+    );
+    assert!(sanity_diagnostics(&Vector::new(), &day_entries, Date::today()).is_empty());
+}
@@ -0,0 +1,170 @@
+use crate::model::{Date, DateRange, DayEntry, Project, Time, TimeRange};
+use crate::report::{day_entries_in_range, render_time};
+use anyhow::Result;
+use derive_getters::Getters;
+use im::Vector;
+
+#[cfg(test)]
+mod tests;
+
+/// Work pattern statistics for the days with time recorded in a date range.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct WorkStats {
+    dates: DateRange,
+    days_worked: usize,
+    weekend_days: usize,
+    average_start: Option<Time>,
+    average_end: Option<Time>,
+    average_day_minutes: u32,
+    longest_day: Option<(Date, u32)>,
+    average_switches: f64,
+    longest_block: Option<(Date, TimeRange)>,
+}
+
+/// Every range worked on a day with its project in order of starting time.
+fn sorted_ranges(entry: &DayEntry) -> Vector<(TimeRange, Project)> {
+    let mut ranges: Vector<(TimeRange, Project)> = entry
+        .projects()
+        .iter()
+        .flat_map(|p| p.time_ranges().iter().map(|r| (*r, p.project().clone())))
+        .collect();
+    ranges.sort();
+    ranges
+}
+
+/// Number of times work moved from one project to another during the day.
+fn project_switches(ranges: &Vector<(TimeRange, Project)>) -> usize {
+    ranges
+        .iter()
+        .zip(ranges.iter().skip(1))
+        .filter(|((_, a), (_, b))| a != b)
+        .count()
+}
+
+/// Ranges with no break between them combined into blocks.  Moving to
+/// another project does not interrupt a block.
+fn blocks(ranges: &Vector<(TimeRange, Project)>) -> Result<Vector<TimeRange>> {
+    let mut answer = Vector::<TimeRange>::new();
+    for (range, _) in ranges {
+        match answer.back_mut() {
+            Some(block) if block.to() == range.from() => {
+                *block = TimeRange::new(*block.from(), *range.to())?;
+            }
+            _ => answer.push_back(*range),
+        }
+    }
+    Ok(answer)
+}
+
+fn average_time(minutes: &[u16]) -> Result<Option<Time>> {
+    if minutes.is_empty() {
+        return Ok(None);
+    }
+    let average = minutes.iter().map(|m| *m as usize).sum::<usize>() / minutes.len();
+    Ok(Some(Time::new(average as u16 / 60, average as u16 % 60)?))
+}
+
+/// Computes statistics for the day entries within the date range.
+pub fn compute_stats(dates: DateRange, day_entries: &Vector<DayEntry>) -> Result<WorkStats> {
+    let entries: Vector<DayEntry> = day_entries_in_range(&dates, day_entries)
+        .into_iter()
+        .filter(|e| e.projects().iter().any(|p| p.total_minutes() > 0))
+        .collect();
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    let mut total_minutes = 0;
+    let mut switches = 0;
+    let mut longest_day: Option<(Date, u32)> = None;
+    let mut longest_block: Option<(Date, TimeRange)> = None;
+    for entry in entries.iter() {
+        let ranges = sorted_ranges(entry);
+        starts.extend(ranges.front().map(|(r, _)| r.from().minute_of_day()));
+        ends.extend(ranges.iter().map(|(r, _)| r.to().minute_of_day()).max());
+        let minutes: u32 = entry.projects().iter().map(|p| p.total_minutes()).sum();
+        total_minutes += minutes;
+        if longest_day.is_none_or(|(_, m)| minutes > m) {
+            longest_day = Some((*entry.date(), minutes));
+        }
+        switches += project_switches(&ranges);
+        for block in blocks(&ranges)? {
+            if longest_block.is_none_or(|(_, b)| block.duration() > b.duration()) {
+                longest_block = Some((*entry.date(), block));
+            }
+        }
+    }
+    let days_worked = entries.len();
+    Ok(WorkStats {
+        dates,
+        days_worked,
+        weekend_days: entries.iter().filter(|e| e.date().is_weekend()).count(),
+        average_start: average_time(&starts)?,
+        average_end: average_time(&ends)?,
+        average_day_minutes: total_minutes / days_worked.max(1) as u32,
+        longest_day,
+        average_switches: switches as f64 / days_worked.max(1) as f64,
+        longest_block,
+    })
+}
+
+fn render_clock(time: &Option<Time>) -> String {
+    time.map_or("-".to_string(), |t| {
+        format!("{:02}:{:02}", t.hour(), t.minute())
+    })
+}
+
+fn render_date(date: &Date) -> String {
+    format!("{} {}", date.day_abbrev(), date)
+}
+
+/// Renders the statistics as labelled lines of text.
+pub fn render_stats(stats: &WorkStats) -> Vector<String> {
+    let line = |label: &str, value: String, detail: String| {
+        format!("{label:24}{value:>6}   {detail}")
+            .trim_end()
+            .to_string()
+    };
+    let mut answer = Vector::new();
+    answer.push_back(line(
+        "Days worked",
+        stats.days_worked.to_string(),
+        String::new(),
+    ));
+    answer.push_back(line(
+        "Weekend days worked",
+        stats.weekend_days.to_string(),
+        String::new(),
+    ));
+    answer.push_back(line(
+        "Average start",
+        render_clock(&stats.average_start),
+        String::new(),
+    ));
+    answer.push_back(line(
+        "Average end",
+        render_clock(&stats.average_end),
+        String::new(),
+    ));
+    answer.push_back(line(
+        "Average day",
+        render_time(stats.average_day_minutes, 3),
+        String::new(),
+    ));
+    answer.push_back(match &stats.longest_day {
+        Some((date, minutes)) => line("Longest day", render_time(*minutes, 3), render_date(date)),
+        None => line("Longest day", "-".to_string(), String::new()),
+    });
+    answer.push_back(line(
+        "Project switches per day",
+        format!("{:.1}", stats.average_switches),
+        String::new(),
+    ));
+    answer.push_back(match &stats.longest_block {
+        Some((date, block)) => line(
+            "Longest block",
+            render_time(block.duration() as u32, 3),
+            format!("{} {}", render_date(date), block),
+        ),
+        None => line("Longest block", "-".to_string(), String::new()),
+    });
+    answer
+}
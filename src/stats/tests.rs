@@ -0,0 +1,74 @@
+use super::*;
+use crate::model::fixtures::{date, project_times, time, time_range};
+use im::vector;
+
+fn day_entries() -> Vector<DayEntry> {
+    let cms = Project::new("acme", "cms", "");
+    let web = Project::new("acme", "web", "");
+    vector!(
+        DayEntry::new(
+            date(2025, 7, 7),
+            &vector!(
+                project_times(
+                    &cms,
+                    vector!(time_range(8, 0, 10, 0), time_range(13, 0, 14, 0))
+                ),
+                project_times(
+                    &web,
+                    vector!(time_range(10, 0, 12, 0), time_range(14, 0, 15, 0))
+                )
+            ),
+            1
+        ),
+        DayEntry::new(
+            date(2025, 7, 12),
+            &vector!(project_times(&cms, vector!(time_range(9, 0, 10, 30)))),
+            5
+        ),
+        DayEntry::new(
+            date(2025, 7, 20),
+            &vector!(project_times(&cms, vector!(time_range(6, 0, 20, 0)))),
+            8
+        )
+    )
+}
+
+#[test]
+fn test_compute_stats() {
+    let dates = DateRange::new(date(2025, 7, 1), date(2025, 7, 15));
+    let stats = compute_stats(dates, &day_entries()).unwrap();
+    assert_eq!(2, stats.days_worked);
+    assert_eq!(1, stats.weekend_days);
+    assert_eq!(Some(time(8, 30)), stats.average_start);
+    assert_eq!(Some(time(12, 45)), stats.average_end);
+    assert_eq!(225, stats.average_day_minutes);
+    assert_eq!(Some((date(2025, 7, 7), 360)), stats.longest_day);
+    assert_eq!(1.5, stats.average_switches);
+    assert_eq!(
+        Some((date(2025, 7, 7), time_range(8, 0, 12, 0))),
+        stats.longest_block
+    );
+    assert_eq!(
+        vector!(
+            "Days worked                  2".to_string(),
+            "Weekend days worked          1".to_string(),
+            "Average start            08:30".to_string(),
+            "Average end              12:45".to_string(),
+            "Average day               3:45".to_string(),
+            "Longest day               6:00   MON 07/07/2025".to_string(),
+            "Project switches per day   1.5".to_string(),
+            "Longest block             4:00   MON 07/07/2025 0800-1200".to_string()
+        ),
+        render_stats(&stats)
+    );
+}
+
+#[test]
+fn test_no_days() {
+    let dates = DateRange::new(date(2025, 8, 1), date(2025, 8, 15));
+    let stats = compute_stats(dates, &day_entries()).unwrap();
+    assert_eq!(0, stats.days_worked);
+    assert_eq!(None, stats.average_start);
+    assert_eq!(None, stats.longest_block);
+    assert_eq!("Longest day                  -", render_stats(&stats)[5]);
+}
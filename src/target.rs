@@ -0,0 +1,124 @@
+use crate::calendar::WorkCalendar;
+use crate::config::ProjectPattern;
+use crate::model::{Date, DateRange, Project};
+use derive_getters::Getters;
+use im::Vector;
+
+#[cfg(test)]
+mod tests;
+
+/// Marker for a project that worked more than its weekly target.
+pub const OVER: &str = "OVER";
+/// Marker for a project that finished a week short of its target.
+pub const UNDER: &str = "UNDER";
+/// Marker for a project short of its pro-rated target from mid-week on.
+pub const BEHIND: &str = "BEHIND";
+
+/// Days into the week from which a project short of its pro-rated target is behind.
+const MID_WEEK_DAYS: u32 = 3;
+
+/// Hours each week expected for every project matching a pattern within a date window.
+#[derive(Debug, PartialEq, Clone, Getters)]
+pub struct Target {
+    pattern: ProjectPattern,
+    minutes: u32,
+    dates: DateRange,
+}
+
+impl Target {
+    pub fn new(pattern: ProjectPattern, minutes: u32, dates: DateRange) -> Self {
+        Target {
+            pattern,
+            minutes,
+            dates,
+        }
+    }
+}
+
+/// Finds the weekly target for a project in a week.  Targets apply to every week
+/// their window overlaps.  The most specific matching pattern wins and within
+/// that the latest start.
+pub fn target_for<'a>(
+    targets: &'a Vector<Target>,
+    project: &Project,
+    week: &DateRange,
+) -> Option<&'a Target> {
+    targets
+        .iter()
+        .filter(|t| {
+            t.pattern.matches(project)
+                && t.dates.first() <= week.last()
+                && t.dates.last() >= week.first()
+        })
+        .max_by_key(|t| (t.pattern.specificity(), *t.dates.first()))
+}
+
+/// Time worked on a project in one week compared with its target.  A week cut
+/// short by the report dates has its target pro-rated over the workdays within
+/// them.  The expected minutes are the target pro-rated over the workdays
+/// before today, since today's time may not be logged yet, or zero before
+/// mid-week when it is too early to tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+pub struct TargetStatus {
+    target_minutes: u32,
+    minutes: u32,
+    expected_minutes: u32,
+    complete: bool,
+}
+
+impl TargetStatus {
+    /// Status of the time worked in the part of the week within the report dates.
+    /// The week is complete once its last date has passed.
+    pub fn new(
+        target_minutes: u32,
+        minutes: u32,
+        week: &DateRange,
+        dates: &DateRange,
+        as_of: &Date,
+        calendar: &WorkCalendar,
+    ) -> Self {
+        let first = *week.first().max(dates.first());
+        let last = *week.last().min(dates.last());
+        let week_workdays = week.iter().filter(|d| calendar.is_workday(d)).count() as u32;
+        let pro_rated = |include: &dyn Fn(&Date) -> bool| {
+            let workdays = week
+                .iter()
+                .filter(|d| first <= *d && *d <= last && include(d) && calendar.is_workday(d))
+                .count() as u32;
+            target_minutes * workdays / week_workdays.max(1)
+        };
+        let days = (as_of.day_num() + 1).saturating_sub(week.first().day_num());
+        let expected_minutes = match days {
+            d if d < MID_WEEK_DAYS => 0,
+            _ => pro_rated(&|d| d < as_of),
+        };
+        TargetStatus {
+            target_minutes: pro_rated(&|_| true),
+            minutes,
+            expected_minutes,
+            complete: *as_of > last,
+        }
+    }
+
+    /// Minutes worked less the target, positive when over.
+    pub fn delta_minutes(&self) -> i32 {
+        self.minutes as i32 - self.target_minutes as i32
+    }
+
+    pub fn is_behind(&self) -> bool {
+        self.minutes < self.expected_minutes
+    }
+
+    /// OVER, UNDER, BEHIND or an empty string when on track.
+    pub fn marker(&self) -> &'static str {
+        if self.minutes > self.target_minutes {
+            OVER
+        } else if self.is_behind() && self.complete {
+            UNDER
+        } else if self.is_behind() {
+            BEHIND
+        } else {
+            ""
+        }
+    }
+}
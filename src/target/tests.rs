@@ -0,0 +1,86 @@
+use super::*;
+use crate::model::fixtures::date;
+use im::{ordset, vector};
+
+fn july(d: u8) -> Date {
+    date(2025, 7, d)
+}
+
+fn week(first: u8, last: u8) -> DateRange {
+    DateRange::new(july(first), july(last))
+}
+
+#[test]
+fn test_target_for() {
+    let targets = vector!(
+        Target::new(
+            ProjectPattern::new("acme", None, None),
+            300,
+            DateRange::new(Date::min_date(), Date::max_date())
+        ),
+        Target::new(
+            ProjectPattern::new("acme", Some("cms"), None),
+            600,
+            DateRange::new(july(16), Date::max_date())
+        )
+    );
+    let cms = Project::new("acme", "cms", "");
+    assert_eq!(
+        300,
+        target_for(&targets, &cms, &week(7, 13)).unwrap().minutes
+    );
+    // The narrower target starts mid-week but still applies to the week.
+    assert_eq!(
+        600,
+        target_for(&targets, &cms, &week(14, 20)).unwrap().minutes
+    );
+    assert_eq!(
+        None,
+        target_for(&targets, &Project::new("bozon", "", ""), &week(14, 20))
+    );
+}
+
+#[test]
+fn test_target_status() {
+    // 07/07/2025 is a Monday.
+    let calendar = WorkCalendar::default();
+    let status = |minutes, dates: &DateRange, as_of| {
+        TargetStatus::new(600, minutes, &week(7, 13), dates, &july(as_of), &calendar)
+    };
+    let whole = week(7, 13);
+    let status_at = status(0, &whole, 8);
+    assert_eq!((0, ""), (status_at.expected_minutes, status_at.marker()));
+    // Today's time may not be logged yet so only earlier workdays are expected.
+    let status_at = status(240, &whole, 9);
+    assert_eq!((240, ""), (status_at.expected_minutes, status_at.marker()));
+    let status_at = status(300, &whole, 10);
+    assert_eq!(
+        (360, BEHIND),
+        (status_at.expected_minutes, status_at.marker())
+    );
+    let status_at = status(600, &whole, 12);
+    assert_eq!((600, ""), (status_at.expected_minutes, status_at.marker()));
+    let status_at = status(540, &whole, 13);
+    assert_eq!(
+        (-60, BEHIND),
+        (status_at.delta_minutes(), status_at.marker())
+    );
+    let status_at = status(540, &whole, 14);
+    assert_eq!(
+        (-60, UNDER),
+        (status_at.delta_minutes(), status_at.marker())
+    );
+    let status_at = status(660, &whole, 9);
+    assert_eq!((60, OVER), (status_at.delta_minutes(), status_at.marker()));
+
+    // Reports starting on Thursday or ending on Tuesday cover two workdays.
+    let status_at = status(240, &DateRange::new(july(10), july(31)), 20);
+    assert_eq!((240, ""), (status_at.target_minutes, status_at.marker()));
+    let status_at = status(200, &DateRange::new(july(1), july(8)), 20);
+    assert_eq!((240, UNDER), (status_at.target_minutes, status_at.marker()));
+
+    // A holiday lowers the target for a week cut short.
+    let calendar = WorkCalendar::new(calendar.workdays(), &ordset!(july(10)));
+    let status_at = TargetStatus::new(600, 0, &whole, &week(9, 13), &july(20), &calendar);
+    assert_eq!(300, status_at.target_minutes);
+}
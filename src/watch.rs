@@ -4,12 +4,14 @@ use ratatui::{
     style::{Color, Modifier, Style},
 };
 
+use crate::config::{self, Config};
+use crate::filter::Filter;
 use crate::model::{Date, DateRange, DayEntry, Project};
 use crate::report;
-use crate::report::ReportMode;
+use crate::report::{ReportLayout, ReportMode};
 use crate::watch::WatchError::EditorExitCode;
 use crate::watch::paragraph::ParagraphBuilder;
-use crate::{append, parse};
+use crate::{alias, append, balance, parse, target};
 use anyhow::Result;
 use crossterm::event::{Event, poll, read};
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -77,6 +79,8 @@ pub fn watch_and_report(filename: &str, dates: &dyn Fn() -> DateRange) -> Result
 enum ScreenEvent {
     Char(char),
     Enter,
+    Backspace,
+    Escape,
     Left,
     Right,
     Resized,
@@ -152,6 +156,8 @@ impl<T: Backend> AppScreen for RealAppScreen<T> {
                     KeyCode::PageUp => return Ok(SCROLL_UP_WEEK),
                     KeyCode::PageDown => return Ok(SCROLL_DOWN_WEEK),
                     KeyCode::Enter => return Ok(ScreenEvent::Enter),
+                    KeyCode::Backspace => return Ok(ScreenEvent::Backspace),
+                    KeyCode::Esc => return Ok(ScreenEvent::Escape),
                     KeyCode::Left => return Ok(ScreenEvent::Left),
                     KeyCode::Right => return Ok(ScreenEvent::Right),
                     _ => {}
@@ -205,15 +211,21 @@ impl Storage for RealStorage {
 
     fn load(&mut self, dates: DateRange, filename: &str) -> Result<LoadedFile> {
         let current_file_millis = self.timestamp(filename)?;
-        let (day_entries, warnings) = parse::parse_file(filename)?;
+        let (all_day_entries, mut warnings) = parse::parse_file(filename)?;
+        let config = config::load_config(&config::config_filename(filename))?;
+        let all_day_entries = alias::apply_aliases(config.aliases(), &all_day_entries);
+        let diagnostics = config.diagnostics(&all_day_entries, Some(&dates), Date::today())?;
+        warnings.extend(diagnostics.iter().map(|d| d.to_string()));
         let min_date = dates.first().minus_days(30)?;
-        let recent_projects = append::recent_projects(&day_entries, min_date, 5);
-        let day_entries = report::day_entries_in_range(&dates, &day_entries);
+        let recent_projects = append::recent_projects(&all_day_entries, min_date, 5);
+        let day_entries = report::day_entries_in_range(&dates, &all_day_entries);
         Ok(LoadedFile::new(
             dates,
+            &all_day_entries,
             &day_entries,
             &warnings,
             &recent_projects,
+            &config,
             current_file_millis,
         ))
     }
@@ -263,25 +275,31 @@ impl Clock for RealClock {
 #[derive(Clone, Getters)]
 struct LoadedFile {
     dates: DateRange,
+    all_day_entries: Vector<DayEntry>,
     day_entries: Vector<DayEntry>,
     warnings: Vector<String>,
     recent_projects: Vector<Project>,
+    config: Config,
     load_time_millis: u128,
 }
 
 impl LoadedFile {
     fn new(
         dates: DateRange,
+        all_day_entries: &Vector<DayEntry>,
         day_entries: &Vector<DayEntry>,
         warnings: &Vector<String>,
         recent_projects: &Vector<Project>,
+        config: &Config,
         load_time_millis: u128,
     ) -> Self {
         LoadedFile {
             dates,
+            all_day_entries: all_day_entries.clone(),
             day_entries: day_entries.clone(),
             warnings: warnings.clone(),
             recent_projects: recent_projects.clone(),
+            config: config.clone(),
             load_time_millis,
         }
     }
@@ -289,9 +307,11 @@ impl LoadedFile {
     fn empty(dates: DateRange) -> Self {
         LoadedFile {
             dates,
+            all_day_entries: Vector::new(),
             day_entries: Vector::new(),
             warnings: Vector::new(),
             recent_projects: Vector::new(),
+            config: Config::default(),
             load_time_millis: 0,
         }
     }
@@ -309,9 +329,34 @@ enum UserRequest {
     Resized,
     Timeout,
     ToggleReportMode,
+    ToggleLayout,
+    Timeline,
+    Heatmap,
+    Filter,
+    FilterChar(char),
+    FilterBackspace,
+    ApplyFilter,
+    CancelFilter,
     Scroll(ScrollAmount),
 }
 
+/// Settings chosen interactively that control how the report is computed.
+struct ReportView {
+    mode: ReportMode,
+    layout: ReportLayout,
+    filter: Filter,
+    tab: ReportTab,
+}
+
+/// Which view of the report is on screen.  Reloads and resizes redraw the
+/// same tab.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ReportTab {
+    Report,
+    Timeline,
+    Heatmap,
+}
+
 enum DisplayContent {
     Report(LoadedFile),
     Warnings(LoadedFile),
@@ -339,7 +384,8 @@ where
     filename: &'a str,
     read_timeout: Duration,
     update_delay_millis: u128,
-    report_mode: ReportMode,
+    view: ReportView,
+    filter_input: Option<String>,
     start_line: usize,
     line_count: usize,
     section_starts: Vector<usize>,
@@ -388,7 +434,13 @@ where
             loaded: LoadedFile::empty(dates()),
             update_delay_millis: 500,
             read_timeout: Duration::from_millis(100),
-            report_mode: ReportMode::Detail,
+            view: ReportView {
+                mode: ReportMode::Detail,
+                layout: ReportLayout::Weekly,
+                filter: Filter::default(),
+                tab: ReportTab::Report,
+            },
+            filter_input: None,
             start_line: 0,
             line_count: 0,
             section_starts: Vector::new(),
@@ -434,9 +486,22 @@ where
 
     fn update_screen(&mut self, what_to_display: &DisplayContent) -> Result<()> {
         match what_to_display {
+            DisplayContent::Report(loaded_file) if self.view.tab == ReportTab::Timeline => {
+                let timeline = format_timeline(loaded_file, &self.view);
+                self.draw_tab(timeline, loaded_file)
+            }
+            DisplayContent::Report(loaded_file) if self.view.tab == ReportTab::Heatmap => {
+                let heatmap = format_heatmap(loaded_file, &self.view);
+                self.draw_tab(heatmap, loaded_file)
+            }
             DisplayContent::Report(loaded_file) => {
-                let report =
-                    ReportScreen::new(&self.menu, loaded_file, self.report_mode, self.start_line);
+                let report = ReportScreen::new(
+                    &self.menu,
+                    loaded_file,
+                    &self.view,
+                    self.filter_input.as_deref(),
+                    self.start_line,
+                );
                 match report {
                     Ok(report) => {
                         self.line_count = report.report.line_count();
@@ -459,6 +524,19 @@ where
         }
     }
 
+    fn draw_tab(&mut self, body: Result<ParagraphBuilder>, file: &LoadedFile) -> Result<()> {
+        match body {
+            Ok(body) => {
+                let screen = TabScreen::new(&self.menu, body, file, self.filter_input.as_deref());
+                self.app_screen.draw(&screen)
+            }
+            Err(error) => {
+                self.app_screen
+                    .draw(&ErrorScreen::new(&self.menu, self.filename, &error))
+            }
+        }
+    }
+
     fn process_user_request(&mut self, event: UserRequest) -> Result<UICommand> {
         let result = match event {
             UserRequest::Quit => Ok(UICommand::Quit),
@@ -471,6 +549,14 @@ where
             UserRequest::Resized => Ok(UICommand::Report(self.loaded.clone())),
             UserRequest::Timeout => self.load(false),
             UserRequest::ToggleReportMode => self.toggle_report_mode(),
+            UserRequest::ToggleLayout => self.toggle_layout(),
+            UserRequest::Timeline => self.toggle_tab(ReportTab::Timeline),
+            UserRequest::Heatmap => self.toggle_tab(ReportTab::Heatmap),
+            UserRequest::Filter => self.edit_filter(event),
+            UserRequest::FilterChar(_) => self.edit_filter(event),
+            UserRequest::FilterBackspace => self.edit_filter(event),
+            UserRequest::ApplyFilter => self.apply_filter(),
+            UserRequest::CancelFilter => self.edit_filter(event),
             UserRequest::Scroll(amount) => self.scroll(amount),
         };
         result.or_else(|e| Ok(UICommand::DisplayError(e)))
@@ -487,7 +573,17 @@ where
 
     fn read_user_request(&mut self) -> Result<UserRequest> {
         loop {
-            match self.app_screen.read(self.read_timeout)? {
+            let event = self.app_screen.read(self.read_timeout)?;
+            if self.filter_input.is_some() {
+                match event {
+                    ScreenEvent::Char(c) => return Ok(UserRequest::FilterChar(c)),
+                    ScreenEvent::Backspace => return Ok(UserRequest::FilterBackspace),
+                    ScreenEvent::Enter => return Ok(UserRequest::ApplyFilter),
+                    ScreenEvent::Escape => return Ok(UserRequest::CancelFilter),
+                    _ => (),
+                }
+            }
+            match event {
                 ScreenEvent::Char(c) => match self.menu.select(c) {
                     Some(x) => {
                         self.menu = x;
@@ -501,6 +597,7 @@ where
                 ScreenEvent::Timeout => return Ok(UserRequest::Timeout),
                 ScreenEvent::Resized => return Ok(UserRequest::Resized),
                 ScreenEvent::Scroll(amount) => return Ok(UserRequest::Scroll(amount)),
+                ScreenEvent::Backspace | ScreenEvent::Escape => continue,
             }
         }
     }
@@ -575,7 +672,44 @@ where
     }
 
     fn toggle_report_mode(&mut self) -> Result<UICommand> {
-        self.report_mode = self.report_mode.toggle();
+        self.view.mode = self.view.mode.next();
+        Ok(UICommand::Report(self.loaded.clone()))
+    }
+
+    fn toggle_layout(&mut self) -> Result<UICommand> {
+        self.view.layout = self.view.layout.next();
+        self.view.tab = ReportTab::Report;
+        self.start_line = 0;
+        Ok(UICommand::Report(self.loaded.clone()))
+    }
+
+    /// Switches to the tab or back to the report when it is already shown.
+    fn toggle_tab(&mut self, tab: ReportTab) -> Result<UICommand> {
+        self.view.tab = if self.view.tab == tab {
+            ReportTab::Report
+        } else {
+            tab
+        };
+        Ok(UICommand::Report(self.loaded.clone()))
+    }
+
+    /// Opens, updates or closes the filter prompt without changing the filter.
+    fn edit_filter(&mut self, user_request: UserRequest) -> Result<UICommand> {
+        match user_request {
+            UserRequest::Filter => self.filter_input = Some(self.view.filter.to_string()),
+            UserRequest::FilterChar(c) => self.filter_input.iter_mut().for_each(|i| i.push(c)),
+            UserRequest::FilterBackspace => {
+                self.filter_input.iter_mut().for_each(|i| _ = i.pop());
+            }
+            _ => self.filter_input = None,
+        }
+        Ok(UICommand::Report(self.loaded.clone()))
+    }
+
+    fn apply_filter(&mut self) -> Result<UICommand> {
+        let input = self.filter_input.take().unwrap_or_default();
+        self.view.filter = Filter::parse(&input)?;
+        self.start_line = 0;
         Ok(UICommand::Report(self.loaded.clone()))
     }
 }
@@ -597,9 +731,33 @@ fn create_menu() -> Result<Menu<UserRequest>> {
         MenuItem::new(
             UserRequest::ToggleReportMode,
             "Mode",
-            "Toggle between detailed and summary report mode.",
+            "Cycle between detail, summary and client report modes.",
             'm'
         ),
+        MenuItem::new(
+            UserRequest::ToggleLayout,
+            "Layout",
+            "Cycle between weekly, monthly, yearly and daily report layouts.",
+            'l'
+        ),
+        MenuItem::new(
+            UserRequest::Timeline,
+            "Timeline",
+            "Display each day as a bar coloured by project or return to the report.",
+            't'
+        ),
+        MenuItem::new(
+            UserRequest::Heatmap,
+            "Heatmap",
+            "Display the time worked by day of the week and hour of the day or return to the report.",
+            'h'
+        ),
+        MenuItem::new(
+            UserRequest::Filter,
+            "Filter",
+            "Limit the report using conditions such as client=acme or tag=meeting.",
+            'f'
+        ),
         MenuItem::new(
             UserRequest::Reload,
             "Reload",
@@ -657,11 +815,33 @@ fn format_warnings_summary(file: &LoadedFile) -> ParagraphBuilder {
         1 => file.warnings.get(0).unwrap().clone(),
         _ => format!(" There are {} warnings.", file.warnings.len()),
     };
+    let title = match file.config.balance() {
+        Some(balance) => {
+            let status = balance::compute_balance_status(
+                balance,
+                &file.all_day_entries,
+                file.config.registry(),
+                *file.dates.last(),
+            );
+            format!(" Warnings | {} ", status.summary())
+        }
+        None => " Warnings ".to_string(),
+    };
+    let mut builder = ParagraphBuilder::new();
+    builder.add_styled(text, style).new_line().titled(title);
+    builder
+}
+
+fn format_filter_prompt(input: &str) -> ParagraphBuilder {
     let mut builder = ParagraphBuilder::new();
     builder
-        .add_styled(text, style)
+        .add_plain(format!(" {input}"))
+        .add_styled(
+            "_".to_string(),
+            Style::new().add_modifier(Modifier::SLOW_BLINK),
+        )
         .new_line()
-        .titled(" Warnings ".to_string());
+        .titled(" Filter (Enter to apply, Esc to cancel) ".to_string());
     builder
 }
 
@@ -683,23 +863,198 @@ fn format_warnings(file: &LoadedFile) -> ParagraphBuilder {
     builder
 }
 
+fn mode_name(mode: ReportMode) -> &'static str {
+    match mode {
+        ReportMode::Detail => "Detail",
+        ReportMode::Summary => "Summary",
+        ReportMode::Client => "Client",
+    }
+}
+
 fn format_report(
     file: &LoadedFile,
-    report_mode: ReportMode,
+    view: &ReportView,
     start_line: usize,
 ) -> Result<ParagraphBuilder> {
     let mut builder = ParagraphBuilder::new();
-    for line in report::create_report(file.dates, &file.day_entries, report_mode)? {
+    let behind_style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
+    for line in report::create_report(
+        file.dates,
+        &file.all_day_entries,
+        view.mode,
+        view.layout,
+        &view.filter,
+        &file.config,
+    )? {
+        builder.add_plain(" ".to_string());
+        if line.ends_with(target::BEHIND) {
+            builder.add_styled(line, behind_style);
+        } else {
+            builder.add_plain(line);
+        }
+        builder.new_line();
+    }
+    let mode = mode_name(view.mode);
+    let mut title = match view.layout {
+        ReportLayout::Weekly => format!(" {mode} Report "),
+        ReportLayout::Monthly => format!(" {mode} Monthly Report "),
+        ReportLayout::Yearly => format!(" {mode} Yearly Report "),
+        ReportLayout::Timeline => format!(" {mode} Timeline Report "),
+        ReportLayout::Daily => format!(" {mode} Daily Report "),
+        ReportLayout::Heatmap => format!(" {mode} Heatmap Report "),
+    };
+    if !view.filter.is_empty() {
+        title += format!("[{}] ", view.filter).as_ref();
+    }
+    builder.titled(title).start_line(start_line);
+    Ok(builder)
+}
+
+/// Colours of the projects in timeline bars in the order of the report's projects.
+const TIMELINE_COLORS: [Color; 12] = [
+    Color::Blue,
+    Color::Yellow,
+    Color::Green,
+    Color::Red,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightBlue,
+    Color::LightYellow,
+    Color::LightGreen,
+    Color::LightRed,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+fn timeline_style(index: Option<usize>) -> Style {
+    match index {
+        Some(i) => Style::new().bg(TIMELINE_COLORS[i % TIMELINE_COLORS.len()]),
+        None => Style::new().fg(Color::DarkGray),
+    }
+}
+
+fn format_timeline(file: &LoadedFile, view: &ReportView) -> Result<ParagraphBuilder> {
+    let report = report::compute_report(
+        file.dates,
+        &file.all_day_entries,
+        view.mode,
+        ReportLayout::Timeline,
+        &view.filter,
+        &file.config,
+    )?;
+    let timeline = report.timeline();
+    let hours: String = timeline
+        .hours()
+        .map(|h| {
+            format!(
+                "{:<width$}",
+                format!("{h:02}"),
+                width = timeline.slots_per_hour()
+            )
+        })
+        .collect();
+    let mut builder = ParagraphBuilder::new();
+    builder
+        .add_plain(format!(" {:9}   {hours}   TOTALS   REPORT", "DATE"))
+        .new_line();
+    for day in timeline.days().iter() {
+        let date = day.date();
+        builder.add_plain(format!(
+            " {} {:02}/{:02}   ",
+            date.day_abbrev(),
+            date.month(),
+            date.day()
+        ));
+        for slot in day.slots(timeline).iter() {
+            let index = slot.as_ref().and_then(|p| report.project_index(p));
+            let text = if slot.is_some() { " " } else { "." };
+            builder.add_styled(text.to_string(), timeline_style(index));
+        }
+        builder
+            .add_plain(format!(
+                "   {:6}   {:6}",
+                report::render_time(*day.totals().minutes(), 3),
+                report::render_time(*day.totals().billable(), 3)
+            ))
+            .new_line();
+    }
+    builder.new_line();
+    for (index, project) in report.projects().iter().enumerate() {
         builder
             .add_plain(" ".to_string())
-            .add_plain(line)
+            .add_styled("  ".to_string(), timeline_style(Some(index)))
+            .add_plain(format!(" {}", project.label()))
             .new_line();
     }
-    let title = match report_mode {
-        ReportMode::Detail => " Detail Report ",
-        ReportMode::Summary => " Summary Report ",
-    };
-    builder.titled(title.to_string()).start_line(start_line);
+    let mode = mode_name(view.mode);
+    let mut title = format!(" {mode} Timeline ");
+    if !view.filter.is_empty() {
+        title += format!("[{}] ", view.filter).as_ref();
+    }
+    builder.titled(title);
+    Ok(builder)
+}
+
+/// Background colours of heatmap cells from no time up to the busiest hours.
+const HEATMAP_COLORS: [Color; 5] = [
+    Color::Reset,
+    Color::Blue,
+    Color::Green,
+    Color::Yellow,
+    Color::Red,
+];
+
+fn heatmap_style(level: usize) -> Style {
+    match level {
+        0 => Style::new().fg(Color::DarkGray),
+        _ => Style::new().bg(HEATMAP_COLORS[level.min(HEATMAP_COLORS.len() - 1)]),
+    }
+}
+
+fn format_heatmap(file: &LoadedFile, view: &ReportView) -> Result<ParagraphBuilder> {
+    let report = report::compute_report(
+        file.dates,
+        &file.all_day_entries,
+        view.mode,
+        ReportLayout::Heatmap,
+        &view.filter,
+        &file.config,
+    )?;
+    let heatmap = report.heatmap();
+    let hours: String = heatmap.hours().map(|h| format!("{h:02} ")).collect();
+    let mut builder = ParagraphBuilder::new();
+    builder
+        .add_plain(format!(" DAY   {hours}  TOTALS"))
+        .new_line();
+    for row in heatmap.rows().iter() {
+        builder.add_plain(format!(" {}   ", row.day()));
+        for minutes in row.hours().iter() {
+            let level = heatmap.level(*minutes);
+            let text = if level == 0 { ". " } else { "  " };
+            builder
+                .add_styled(text.to_string(), heatmap_style(level))
+                .add_plain(" ".to_string());
+        }
+        builder
+            .add_plain(format!("  {:6}", report::render_time(*row.total(), 3)))
+            .new_line();
+    }
+    builder.new_line().add_plain(" ".to_string());
+    for level in 1..HEATMAP_COLORS.len() {
+        builder
+            .add_styled("  ".to_string(), heatmap_style(level))
+            .add_plain(format!(
+                " up to {}   ",
+                report::render_time(heatmap.level_limit(level), 1).trim()
+            ));
+    }
+    builder.new_line();
+    let mode = mode_name(view.mode);
+    let mut title = format!(" {mode} Heatmap ");
+    if !view.filter.is_empty() {
+        title += format!("[{}] ", view.filter).as_ref();
+    }
+    builder.titled(title);
     Ok(builder)
 }
 
@@ -739,13 +1094,15 @@ impl ReportScreen {
     fn new(
         menu: &Menu<UserRequest>,
         file: &LoadedFile,
-        report_mode: ReportMode,
+        view: &ReportView,
+        filter_input: Option<&str>,
         start_line: usize,
     ) -> Result<Self> {
         let screen = ReportScreen {
             menu: format_menu(menu),
-            report: format_report(file, report_mode, start_line)?,
-            warnings: format_warnings_summary(file),
+            report: format_report(file, view, start_line)?,
+            warnings: filter_input
+                .map_or_else(|| format_warnings_summary(file), format_filter_prompt),
         };
         Ok(screen)
     }
@@ -765,6 +1122,43 @@ impl Renderable for ReportScreen {
     }
 }
 
+/// Screen for the timeline and heatmap tabs.
+struct TabScreen {
+    menu: ParagraphBuilder,
+    body: ParagraphBuilder,
+    warnings: ParagraphBuilder,
+}
+
+impl TabScreen {
+    fn new(
+        menu: &Menu<UserRequest>,
+        body: ParagraphBuilder,
+        file: &LoadedFile,
+        filter_input: Option<&str>,
+    ) -> Self {
+        TabScreen {
+            menu: format_menu(menu),
+            body,
+            warnings: filter_input
+                .map_or_else(|| format_warnings_summary(file), format_filter_prompt),
+        }
+    }
+}
+
+impl Renderable for TabScreen {
+    fn render(&self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        use Constraint::{Length, Min};
+        let vertical = Layout::vertical([Length(MENU_HEIGHT), Min(0), Length(WARNING_HEIGHT)]);
+        let [menu_area, body_area, warnings_area] = vertical.areas(area);
+        self.menu.build().render(menu_area, buf);
+        self.body.build().render(body_area, buf);
+        self.warnings.build().render(warnings_area, buf);
+    }
+}
+
 struct WarningsScreen {
     menu: ParagraphBuilder,
     warnings: ParagraphBuilder,
@@ -885,6 +1279,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tab_kept_on_resize_and_reload() {
+        let dates = || DateRange::new(Date::min_date(), Date::max_date());
+        let mut app_screen = MockAppScreen::new();
+        let mut storage = MockStorage::new();
+        storage
+            .expect_load()
+            .returning(|dates, _| Ok(LoadedFile::empty(dates)));
+        let mut editor = MockEditor::new();
+        let mut clock = MockClock::new();
+        let mut app = WatchApp::new(
+            "times.txt",
+            &dates,
+            create_menu().unwrap(),
+            &mut app_screen,
+            &mut storage,
+            &mut editor,
+            &mut clock,
+        );
+        app.process_user_request(UserRequest::Timeline).unwrap();
+        assert_eq!(ReportTab::Timeline, app.view.tab);
+        app.process_user_request(UserRequest::Resized).unwrap();
+        app.process_user_request(UserRequest::Reload).unwrap();
+        assert_eq!(ReportTab::Timeline, app.view.tab);
+        app.process_user_request(UserRequest::Timeline).unwrap();
+        assert_eq!(ReportTab::Report, app.view.tab);
+        app.process_user_request(UserRequest::Timeline).unwrap();
+        app.process_user_request(UserRequest::ToggleLayout).unwrap();
+        assert_eq!(ReportTab::Report, app.view.tab);
+        assert_eq!(ReportLayout::Monthly, app.view.layout);
+        app.process_user_request(UserRequest::Heatmap).unwrap();
+        app.process_user_request(UserRequest::Resized).unwrap();
+        app.process_user_request(UserRequest::Reload).unwrap();
+        assert_eq!(ReportTab::Heatmap, app.view.tab);
+        app.process_user_request(UserRequest::Timeline).unwrap();
+        assert_eq!(ReportTab::Timeline, app.view.tab);
+    }
+
     #[test]
     fn test_offsets() {
         let start_offsets: Vector<usize> = vector!(0, 7, 12);
@@ -78,7 +78,7 @@ impl ParagraphBuilder {
         self
     }
 
-    pub fn build(&self) -> Paragraph {
+    pub fn build(&self) -> Paragraph<'_> {
         let line_count = Ord::max(1, self.lines.len());
         let skip = Ord::min(line_count - 1, self.start_line);
         let lines: Vec<Line> = self